@@ -0,0 +1,512 @@
+//! Parses the product catalogs that producers send as spreadsheets exported to CSV
+//!
+//! Every row is checked before anything is committed, so that the whole catalog can be
+//! fixed in one go instead of failing on the first bad row
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Same variants, and serialization, as the `PackagingUnit` of the producers zome
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PackagingUnit {
+    Piece,
+    Kilograms,
+    Grams,
+    Liters,
+    Milliliters,
+    Pounds,
+    Ounces,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Packaging {
+    pub unit: PackagingUnit,
+    pub number_of_packages: u32,
+    pub amount_per_package: f32,
+    pub estimate: bool,
+}
+
+/// A `Product` of the producers zome, without the `producer_hash` which the catalog doesn't know about
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CatalogProduct {
+    pub name: String,
+    pub product_id: String,
+    pub description: String,
+    pub categories: Vec<String>,
+    pub packaging: Packaging,
+    pub maximum_available: Option<u32>,
+    pub price_cents: u32,
+    pub vat_percentage: f32,
+    pub margin_percentage: Option<f32>,
+    pub origin: Option<String>,
+    pub ingredients: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CatalogRowError {
+    /// Line number in the file, starting at 1 for the header
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct CatalogImport {
+    pub products: Vec<CatalogProduct>,
+    pub errors: Vec<CatalogRowError>,
+}
+
+impl CatalogImport {
+    fn error(&mut self, line: usize, message: String) {
+        self.errors.push(CatalogRowError { line, message });
+    }
+}
+
+/// Splits the contents of a CSV file into rows of fields, following RFC 4180 quoting
+fn csv_rows(contents: &str) -> Vec<Vec<String>> {
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut row: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            ',' => row.push(std::mem::take(&mut field)),
+            '\r' | '\n' => {
+                if c == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+/// Parses positive numbers written with either a decimal point or a decimal comma,
+/// ignoring anything around them, e.g. "3,50 €" or "12%"
+fn parse_decimal(label: &str, field: &str) -> Result<f64, String> {
+    let unrecognized = || format!("Unrecognized {label} \"{field}\"");
+    let number_start = field
+        .find(|c: char| c.is_ascii_digit())
+        .ok_or_else(unrecognized)?;
+    if field[..number_start].contains('-') {
+        return Err(format!("The {label} \"{field}\" can't be negative"));
+    }
+    let number: String = field[number_start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        .collect();
+    number.replace(',', ".").parse().map_err(|_| unrecognized())
+}
+
+pub fn parse_unit(unit: &str) -> Option<PackagingUnit> {
+    match unit.trim().to_lowercase().as_str() {
+        "kilograms" | "kilogram" | "kg" => Some(PackagingUnit::Kilograms),
+        "grams" | "gram" | "g" | "gr" => Some(PackagingUnit::Grams),
+        "liters" | "liter" | "l" => Some(PackagingUnit::Liters),
+        "milliliters" | "milliliter" | "ml" => Some(PackagingUnit::Milliliters),
+        "ounces" | "ounce" | "oz" => Some(PackagingUnit::Ounces),
+        "pounds" | "pound" | "lb" => Some(PackagingUnit::Pounds),
+        "pieces" | "piece" | "pcs" | "p" | "st" => Some(PackagingUnit::Piece),
+        _ => None,
+    }
+}
+
+/// Parses an amount with its unit, e.g. "500ml", "0,5 kg" or "5liters"
+fn parse_amount_and_unit(field: &str) -> Option<(f32, PackagingUnit)> {
+    let field = field.trim();
+    let unit_start = field.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == ','))?;
+    let (amount, unit) = field.split_at(unit_start);
+    let amount = amount.replace(',', ".").parse::<f32>().ok()?;
+    Some((amount, parse_unit(unit)?))
+}
+
+/// Parses packagings like "4x500g", "500g x 4" or "1kg", with a leading "~" for estimated amounts
+pub fn parse_packaging(field: &str) -> Option<Packaging> {
+    let field = field.trim();
+    let (estimate, field) = match field.strip_prefix('~') {
+        Some(field) => (true, field.trim()),
+        None => (false, field),
+    };
+
+    let parts: Vec<&str> = field.split(['x', 'X']).map(|part| part.trim()).collect();
+    let (number_of_packages, amount) = match parts.as_slice() {
+        [amount] => (1, *amount),
+        [first, second] => match first.parse::<u32>() {
+            Ok(number_of_packages) => (number_of_packages, *second),
+            Err(_) => (second.parse::<u32>().ok()?, *first),
+        },
+        _ => return None,
+    };
+    let (amount_per_package, unit) = parse_amount_and_unit(amount)?;
+
+    Some(Packaging {
+        unit,
+        number_of_packages,
+        amount_per_package,
+        estimate,
+    })
+}
+
+struct Columns {
+    name: usize,
+    product_id: usize,
+    price: usize,
+    vat: usize,
+    packaging: usize,
+    description: Option<usize>,
+    categories: Option<usize>,
+    margin: Option<usize>,
+    origin: Option<usize>,
+    ingredients: Option<usize>,
+    maximum_available: Option<usize>,
+    multiplier: Option<usize>,
+}
+
+/// The column whose header is exactly one of the names or, if there is none,
+/// the only column whose header contains one of them
+fn find_column(headers: &[String], label: &str, names: &[&str]) -> Result<Option<usize>, String> {
+    let headers: Vec<String> = headers
+        .iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect();
+    if let Some(column) = headers
+        .iter()
+        .position(|header| names.contains(&header.as_str()))
+    {
+        return Ok(Some(column));
+    }
+
+    let columns: Vec<usize> = headers
+        .iter()
+        .enumerate()
+        .filter(|(_, header)| names.iter().any(|name| header.contains(name)))
+        .map(|(column, _)| column)
+        .collect();
+    match columns.as_slice() {
+        [] => Ok(None),
+        [column] => Ok(Some(*column)),
+        _ => Err(format!(
+            "There are several columns that could be the \"{label}\" column"
+        )),
+    }
+}
+
+fn find_columns(headers: &[String], import: &mut CatalogImport) -> Option<Columns> {
+    let mut errors: Vec<String> = Vec::new();
+    let mut find =
+        |label: &str, names: &[&str], required: bool| match find_column(headers, label, names) {
+            Ok(None) if required => {
+                errors.push(format!("There is no \"{label}\" column"));
+                None
+            }
+            Ok(column) => Some(column),
+            Err(message) => {
+                errors.push(message);
+                None
+            }
+        };
+    let product_id = find("Product ID", &["product id", "product_id"], true);
+    let name = find("Name", &["name"], true);
+    let price = find("Price", &["price"], true);
+    let vat = find("VAT", &["vat"], true);
+    let packaging = find("Packaging", &["packaging"], true);
+    let description = find("Description", &["description"], false);
+    let categories = find("Categories", &["categor"], false);
+    let margin = find("Margin", &["margin"], false);
+    let origin = find("Origin", &["origin"], false);
+    let ingredients = find("Ingredients", &["ingredients"], false);
+    let maximum_available = find("Maximum available", &["maximum", "available"], false);
+
+    let required_columns = [
+        ("Product ID", product_id),
+        ("Name", name),
+        ("Price", price),
+        ("VAT", vat),
+        ("Packaging", packaging),
+    ];
+    for (i, (label, column)) in required_columns.iter().enumerate() {
+        for (other_label, other_column) in &required_columns[i + 1..] {
+            if let (Some(Some(column)), Some(Some(other_column))) = (column, other_column) {
+                if column == other_column {
+                    errors.push(format!(
+                        "The \"{}\" column can't be both the \"{label}\" and the \"{other_label}\" column",
+                        headers[*column].trim()
+                    ));
+                }
+            }
+        }
+    }
+    if !errors.is_empty() {
+        for message in errors {
+            import.error(1, message);
+        }
+        return None;
+    }
+
+    Some(Columns {
+        name: name.flatten()?,
+        product_id: product_id.flatten()?,
+        price: price.flatten()?,
+        vat: vat.flatten()?,
+        packaging: packaging.flatten()?,
+        description: description?,
+        categories: categories?,
+        margin: margin?,
+        origin: origin?,
+        ingredients: ingredients?,
+        maximum_available: maximum_available?,
+        multiplier: headers.iter().position(|header| header.trim() == "x"),
+    })
+}
+
+fn optional_field(fields: &[String], column: Option<usize>) -> Option<String> {
+    column
+        .map(|column| fields[column].trim().to_string())
+        .filter(|field| !field.is_empty())
+}
+
+fn parse_row(columns: &Columns, fields: &[String]) -> Result<CatalogProduct, String> {
+    let product_id = fields[columns.product_id].trim().to_string();
+    if product_id.is_empty() {
+        return Err(String::from("The product ID is empty"));
+    }
+    let name = fields[columns.name].trim().to_string();
+    if name.is_empty() {
+        return Err(String::from("The name is empty"));
+    }
+
+    let mut price = parse_decimal("price", &fields[columns.price])?;
+    if let Some(multiplier) = columns.multiplier {
+        price *= parse_decimal("multiplier", &fields[multiplier])?;
+    }
+
+    let vat_field = &fields[columns.vat];
+    let vat_percentage = parse_decimal("VAT", vat_field)? as f32;
+    if vat_percentage > 100.0 {
+        return Err(format!("VAT \"{vat_field}\" is more than 100%"));
+    }
+
+    let packaging_field = &fields[columns.packaging];
+    let packaging = parse_packaging(packaging_field)
+        .ok_or(format!("Unrecognized packaging \"{packaging_field}\""))?;
+
+    let margin_percentage = match optional_field(fields, columns.margin) {
+        Some(margin) => Some(parse_decimal("margin", &margin)? as f32),
+        None => None,
+    };
+    let maximum_available = match optional_field(fields, columns.maximum_available) {
+        Some(maximum) => Some(
+            maximum
+                .parse::<u32>()
+                .map_err(|_| format!("Unrecognized maximum available \"{maximum}\""))?,
+        ),
+        None => None,
+    };
+    let categories = optional_field(fields, columns.categories)
+        .map(|categories| {
+            categories
+                .split(',')
+                .map(|category| category.trim().to_string())
+                .filter(|category| !category.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(CatalogProduct {
+        name,
+        product_id,
+        description: optional_field(fields, columns.description).unwrap_or_default(),
+        categories,
+        packaging,
+        maximum_available,
+        price_cents: (price * 100.0).round() as u32,
+        vat_percentage,
+        margin_percentage,
+        origin: optional_field(fields, columns.origin),
+        ingredients: optional_field(fields, columns.ingredients),
+    })
+}
+
+/// Parses the products in the given CSV, whose first line must have the column names
+///
+/// Rows with errors are reported in `errors` and left out of `products`
+pub fn parse_catalog(contents: &str) -> CatalogImport {
+    let mut import = CatalogImport::default();
+    let mut rows = csv_rows(contents.trim_start_matches('\u{feff}')).into_iter();
+
+    let Some(headers) = rows.next() else {
+        import.error(1, String::from("The file is empty"));
+        return import;
+    };
+    let Some(columns) = find_columns(&headers, &mut import) else {
+        return import;
+    };
+
+    // Line of the first row with each product ID
+    let mut product_ids: BTreeMap<String, usize> = BTreeMap::new();
+    for (i, fields) in rows.enumerate() {
+        let line = i + 2;
+        if fields.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        if fields.len() != headers.len() {
+            import.error(
+                line,
+                format!(
+                    "The line has {} columns, but the header has {}",
+                    fields.len(),
+                    headers.len()
+                ),
+            );
+            continue;
+        }
+        match parse_row(&columns, &fields) {
+            Ok(product) => {
+                if let Some(first_line) = product_ids.get(&product.product_id) {
+                    import.error(
+                        line,
+                        format!(
+                            "The product ID \"{}\" is repeated from line {first_line}",
+                            product.product_id
+                        ),
+                    );
+                    continue;
+                }
+                product_ids.insert(product.product_id.clone(), line);
+                import.products.push(product);
+            }
+            Err(message) => import.error(line, message),
+        }
+    }
+    import
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "Product ID,Name,Description,Category,Packaging,Price,VAT\n";
+
+    #[test]
+    fn parses_valid_rows() {
+        let import = parse_catalog(&format!(
+            "{HEADER}1,Rice,Beautiful rice,\"rice, cereals\",1x1Kg,3 €,12%\n2,Beans,,bean,4x500g,\"8,50 €\",15\n"
+        ));
+        assert_eq!(import.errors, vec![]);
+        assert_eq!(import.products.len(), 2);
+
+        let rice = &import.products[0];
+        assert_eq!(rice.product_id, "1");
+        assert_eq!(rice.categories, vec!["rice", "cereals"]);
+        assert_eq!(rice.price_cents, 300);
+        assert_eq!(rice.vat_percentage, 12.0);
+        assert_eq!(rice.packaging.unit, PackagingUnit::Kilograms);
+
+        let beans = &import.products[1];
+        assert_eq!(beans.price_cents, 850);
+        assert_eq!(beans.packaging.number_of_packages, 4);
+        assert_eq!(beans.packaging.amount_per_package, 500.0);
+        assert_eq!(beans.packaging.unit, PackagingUnit::Grams);
+    }
+
+    #[test]
+    fn reports_every_bad_row() {
+        let import = parse_catalog(&format!(
+            "{HEADER}1,Rice,,rice,1x1Kg,3 €,12%\n2,Beans,,bean,4 boxes,8 €,15%\n,Lentils,,,1kg,2 €,4%\n4,Oil,,,1l,free,4%\n1,Rice again,,,1kg,3 €,12%\n5,Short\n"
+        ));
+        assert_eq!(import.products.len(), 1);
+        let lines: Vec<usize> = import.errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, vec![3, 4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn reports_missing_columns() {
+        let import = parse_catalog("Name,Price\nRice,3\n");
+        assert!(import.products.is_empty());
+        assert_eq!(import.errors.len(), 3);
+        assert!(import.errors.iter().all(|error| error.line == 1));
+    }
+
+    #[test]
+    fn prefers_exact_headers_and_reports_ambiguous_ones() {
+        let import = parse_catalog(
+            "Product ID,Name,Packaging,Price incl. VAT,VAT\n1,Rice,1kg,\"3,30 €\",10%\n",
+        );
+        assert_eq!(import.errors, vec![]);
+        assert_eq!(import.products[0].price_cents, 330);
+        assert_eq!(import.products[0].vat_percentage, 10.0);
+
+        let import = parse_catalog(
+            "Product ID,Name,Packaging,Price,VAT %,VAT amount\n1,Rice,1kg,3,10%,0.3\n",
+        );
+        assert!(import.products.is_empty());
+        assert_eq!(import.errors.len(), 1);
+        assert!(import.errors[0].message.contains("several columns"));
+
+        let import = parse_catalog("Product ID,Name,Packaging,Price incl. VAT\n1,Rice,1kg,3\n");
+        assert!(import.products.is_empty());
+        assert_eq!(import.errors.len(), 1);
+        assert!(import.errors[0].message.contains("can't be both"));
+    }
+
+    #[test]
+    fn rejects_negative_values() {
+        let import = parse_catalog(&format!(
+            "{HEADER}1,Rice,,,1kg,-3 €,12%\n2,Beans,,,1kg,€ -3,12%\n3,Oil,,,1l,3 €,-12%\n4,Salt,,,1kg,3 €,12%\n"
+        ));
+        assert_eq!(import.products.len(), 1);
+        assert_eq!(import.products[0].product_id, "4");
+        let lines: Vec<usize> = import.errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, vec![2, 3, 4]);
+        assert!(import
+            .errors
+            .iter()
+            .all(|error| error.message.contains("can't be negative")));
+    }
+
+    #[test]
+    fn parses_packagings() {
+        assert_eq!(
+            parse_packaging("500g x 4"),
+            Some(Packaging {
+                unit: PackagingUnit::Grams,
+                number_of_packages: 4,
+                amount_per_package: 500.0,
+                estimate: false,
+            })
+        );
+        assert_eq!(
+            parse_packaging("~0,5 kg"),
+            Some(Packaging {
+                unit: PackagingUnit::Kilograms,
+                number_of_packages: 1,
+                amount_per_package: 0.5,
+                estimate: true,
+            })
+        );
+        assert_eq!(parse_packaging("6 pcs").unwrap().unit, PackagingUnit::Piece);
+        assert_eq!(parse_packaging("2x3x1kg"), None);
+        assert_eq!(parse_packaging("a bag"), None);
+    }
+}
@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+/// Most decimal places a currency can have, which is also what the UI offers
+pub const MAX_DECIMAL_PLACES: u8 = 4;
+
+/// Settings of the buyers club, written in the `club_settings` field of the DNA properties
+/// when the club is created
+///
+/// All the amounts of money in the DNA (e.g. `price_cents`) are in the minor unit of the currency,
+/// which has `decimal_places` decimals
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ClubSettings {
+    /// ISO 4217 code, e.g. "EUR"
+    pub currency_code: String,
+    pub decimal_places: u8,
+}
+
+impl Default for ClubSettings {
+    fn default() -> Self {
+        ClubSettings {
+            currency_code: String::from("EUR"),
+            decimal_places: 2,
+        }
+    }
+}
+
+impl ClubSettings {
+    /// Checks the settings before they get written in the DNA properties,
+    /// since they can come from the query parameters of an invite link
+    pub fn validate(&self) -> Result<(), String> {
+        if self.currency_code.trim().is_empty() {
+            return Err(String::from("The currency code can't be empty"));
+        }
+        if self.decimal_places > MAX_DECIMAL_PLACES {
+            return Err(format!(
+                "A currency can have at most {MAX_DECIMAL_PLACES} decimal places, got {}",
+                self.decimal_places
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn too_many_decimal_places_or_an_empty_currency_are_invalid() {
+        assert!(ClubSettings {
+            currency_code: String::from("XXX"),
+            decimal_places: 25,
+        }
+        .validate()
+        .is_err());
+
+        assert!(ClubSettings::default().validate().is_ok());
+        assert!(ClubSettings {
+            currency_code: String::from("EUR"),
+            decimal_places: MAX_DECIMAL_PLACES,
+        }
+        .validate()
+        .is_ok());
+        assert!(ClubSettings {
+            currency_code: String::from(" "),
+            decimal_places: 2,
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,97 @@
+//! Pricing for products that are sold by nominal package but delivered by weight or volume
+//!
+//! A product with an estimated packaging is priced per package of `amount_per_package`
+//! (e.g. 500 cents for a 1 kg bag of potatoes), but each household gets the real weight
+//! of what they were handed, so the price is scaled by the delivered weight
+
+/// Number of packages the delivered amount is billed as
+///
+/// For estimated products this is the delivered weight or volume over the amount per package.
+/// Fixed amount products, and estimated products without a valid amount per package,
+/// are billed by the delivered amount as is
+pub fn billed_packages(estimate: bool, amount_per_package: f32, delivered_amount: f32) -> f64 {
+    if !delivered_amount.is_finite() || delivered_amount <= 0.0 {
+        return 0.0;
+    }
+    if estimate && amount_per_package.is_finite() && amount_per_package > 0.0 {
+        delivered_amount as f64 / amount_per_package as f64
+    } else {
+        delivered_amount as f64
+    }
+}
+
+/// Adds up the weights of each of the delivered items, ignoring invalid ones
+pub fn total_weight(weights: &[f32]) -> f32 {
+    weights
+        .iter()
+        .filter(|weight| weight.is_finite() && **weight > 0.0)
+        .sum()
+}
+
+/// Price in cents of the delivered amount, before rounding
+pub fn price_cents(
+    estimate: bool,
+    amount_per_package: f32,
+    price_cents_per_package: u32,
+    delivered_amount: f32,
+) -> f64 {
+    billed_packages(estimate, amount_per_package, delivered_amount) * price_cents_per_package as f64
+}
+
+/// Rounds an amount of cents to the nearest cent, halves away from zero
+pub fn round_cents(cents: f64) -> u64 {
+    if !cents.is_finite() || cents <= 0.0 {
+        return 0;
+    }
+    cents.round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn estimated_products_are_priced_by_weight() {
+        // 1.2 kg of a product priced 500 cents per 1 kg package
+        assert_eq!(round_cents(price_cents(true, 1.0, 500, 1.2)), 600);
+        // 750 g of a product priced 300 cents per 500 g package
+        assert_eq!(round_cents(price_cents(true, 500.0, 300, 750.0)), 450);
+    }
+
+    #[test]
+    fn fixed_amount_products_are_priced_by_package() {
+        assert_eq!(round_cents(price_cents(false, 1.0, 500, 3.0)), 1500);
+        // The amount per package doesn't matter for fixed amount products
+        assert_eq!(round_cents(price_cents(false, 250.0, 500, 3.0)), 1500);
+    }
+
+    #[test]
+    fn invalid_amount_per_package_falls_back_to_the_delivered_amount() {
+        assert_eq!(billed_packages(true, 0.0, 2.0), 2.0);
+        assert_eq!(billed_packages(true, -1.0, 2.0), 2.0);
+        assert_eq!(billed_packages(true, f32::NAN, 2.0), 2.0);
+    }
+
+    #[test]
+    fn invalid_delivered_amounts_are_not_billed() {
+        assert_eq!(billed_packages(true, 1.0, 0.0), 0.0);
+        assert_eq!(billed_packages(true, 1.0, -0.5), 0.0);
+        assert_eq!(billed_packages(false, 1.0, f32::NAN), 0.0);
+        assert_eq!(billed_packages(false, 1.0, f32::INFINITY), 0.0);
+    }
+
+    #[test]
+    fn total_weight_ignores_invalid_weights() {
+        assert_eq!(total_weight(&[0.5, 0.25, 0.25]), 1.0);
+        assert_eq!(total_weight(&[0.5, f32::NAN, -1.0, 0.5]), 1.0);
+        assert_eq!(total_weight(&[]), 0.0);
+    }
+
+    #[test]
+    fn cents_are_rounded_to_the_nearest_cent() {
+        assert_eq!(round_cents(149.4), 149);
+        assert_eq!(round_cents(149.5), 150);
+        assert_eq!(round_cents(-3.0), 0);
+        assert_eq!(round_cents(f64::NAN), 0);
+    }
+}
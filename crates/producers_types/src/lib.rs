@@ -20,3 +20,64 @@ pub struct Producer {
     pub liason: AgentPubKey,
     pub editors: ProducerEditors,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum PackagingUnit {
+    Piece,
+    Kilograms,
+    Grams,
+    Liters,
+    Milliliters,
+    Pounds,
+    Ounces,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Packaging {
+    pub unit: PackagingUnit,
+    pub number_of_packages: u32,
+    pub amount_per_package: f32,
+    pub estimate: bool,
+}
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Product {
+    pub producer_hash: ActionHash,
+    /// Revision of the producer whose editors can make this revision of the product
+    pub latest_producer_hash: ActionHash,
+    pub name: String,
+    pub product_id: String,
+    pub description: String,
+    pub categories: Vec<String>,
+    pub packaging: Packaging,
+    pub maximum_available: Option<u32>,
+    pub price_cents: u32,
+    pub vat_percentage: f32,
+    pub margin_percentage: Option<f32>,
+    pub origin: Option<String>,
+    pub ingredients: Option<String>,
+}
+
+/// Whether the given action is the original action or one of the updates that follow from it
+///
+/// Integrity zomes use it to check the `latest_*_hash` fields that the coordinators set, e.g.
+/// `Product::latest_producer_hash`. It only walks back from the revision to the original:
+/// it can't tell whether that revision was already updated, so a revision that is no longer
+/// the latest one still passes
+pub fn is_revision_of(
+    revision_hash: &ActionHash,
+    original_hash: &ActionHash,
+) -> ExternResult<bool> {
+    let mut revision_hash = revision_hash.clone();
+    loop {
+        if revision_hash.eq(original_hash) {
+            return Ok(true);
+        }
+        let action = must_get_action(revision_hash)?;
+        match action.action() {
+            Action::Update(update) => {
+                revision_hash = update.original_action_address.clone();
+            }
+            _ => return Ok(false),
+        }
+    }
+}
@@ -1,11 +1,195 @@
+use std::collections::BTreeSet;
+
 use hdk::prelude::*;
 use orders_integrity::*;
 
+use crate::order::get_latest_order;
+
+/// Orders created before the month and status indexes existed are only linked from here
+fn legacy_all_orders_path() -> Path {
+    Path::from("all_orders")
+}
+
+fn get_links_batch(inputs: Vec<GetLinksInput>) -> ExternResult<Vec<Link>> {
+    let links = HDK.with(|hdk| hdk.borrow().get_links(inputs))?;
+    Ok(links.into_iter().flatten().collect())
+}
+
+/// Keeps the first link for each target, in case an order is indexed more than once
+fn unique_targets(links: Vec<Link>) -> Vec<Link> {
+    let mut targets: BTreeSet<AnyLinkableHash> = BTreeSet::new();
+    links
+        .into_iter()
+        .filter(|link| targets.insert(link.target.clone()))
+        .collect()
+}
+
+/// Returns every order: the ones in the status indexes, plus the ones that were
+/// only linked from the legacy all_orders path and haven't been migrated yet
 #[hdk_extern]
 pub fn get_all_orders() -> ExternResult<Vec<Link>> {
-    let path = Path::from("all_orders");
-    get_links(
-        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllOrders)?
+    let mut inputs = vec![GetLinksInputBuilder::try_new(
+        legacy_all_orders_path().path_entry_hash()?,
+        LinkTypes::AllOrders,
+    )?
+    .build()];
+    for status_name in ORDER_STATUS_NAMES {
+        inputs.push(
+            GetLinksInputBuilder::try_new(
+                orders_by_status_path(status_name).path_entry_hash()?,
+                LinkTypes::OrdersByStatus,
+            )?
             .build(),
+        );
+    }
+    Ok(unique_targets(get_links_batch(inputs)?))
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetOrdersInRangeInput {
+    pub from: Timestamp,
+    pub to: Timestamp,
+}
+
+/// Returns the orders created between the given timestamps,
+/// only reading the month paths that overlap with the range
+#[hdk_extern]
+pub fn get_orders_in_range(input: GetOrdersInRangeInput) -> ExternResult<Vec<Link>> {
+    let (mut year, mut month) = year_and_month(input.from);
+    let (to_year, to_month) = year_and_month(input.to);
+
+    let mut inputs: Vec<GetLinksInput> = Vec::new();
+    while (year, month) <= (to_year, to_month) {
+        inputs.push(
+            GetLinksInputBuilder::try_new(
+                orders_by_year_and_month_path(year, month).path_entry_hash()?,
+                LinkTypes::OrdersByMonth,
+            )?
+            .build(),
+        );
+        if month == 12 {
+            year += 1;
+            month = 1;
+        } else {
+            month += 1;
+        }
+    }
+
+    Ok(get_links_batch(inputs)?
+        .into_iter()
+        .filter(|link| {
+            let created_at = order_created_at(&link.tag).unwrap_or(link.timestamp);
+            created_at >= input.from && created_at <= input.to
+        })
+        .collect())
+}
+
+/// Returns the orders whose latest revision has the given status,
+/// e.g. "Open" for the orders households can currently order in
+#[hdk_extern]
+pub fn get_orders_by_status(status_name: String) -> ExternResult<Vec<Link>> {
+    if !ORDER_STATUS_NAMES.contains(&status_name.as_str()) {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Unknown order status: {status_name}"
+        ))));
+    }
+    get_links(
+        GetLinksInputBuilder::try_new(
+            orders_by_status_path(&status_name).path_entry_hash()?,
+            LinkTypes::OrdersByStatus,
+        )?
+        .build(),
     )
 }
+
+pub fn index_order_by_month(order_hash: ActionHash, created_at: Timestamp) -> ExternResult<()> {
+    create_link(
+        orders_by_month_path(created_at).path_entry_hash()?,
+        order_hash,
+        LinkTypes::OrdersByMonth,
+        orders_by_month_tag(created_at),
+    )?;
+    Ok(())
+}
+
+pub fn index_order_by_status(order_hash: ActionHash, status: &OrderStatus) -> ExternResult<()> {
+    create_link(
+        orders_by_status_path(status.name()).path_entry_hash()?,
+        order_hash,
+        LinkTypes::OrdersByStatus,
+        (),
+    )?;
+    Ok(())
+}
+
+/// Removes the given order from the status index, whichever status it's indexed under
+pub fn unindex_order_by_status(order_hash: &ActionHash) -> ExternResult<()> {
+    for status_name in ORDER_STATUS_NAMES {
+        let links = get_links(
+            GetLinksInputBuilder::try_new(
+                orders_by_status_path(status_name).path_entry_hash()?,
+                LinkTypes::OrdersByStatus,
+            )?
+            .build(),
+        )?;
+        for link in links {
+            if link.target.clone().into_action_hash().as_ref() == Some(order_hash) {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn unindex_order_by_month(order_hash: &ActionHash, created_at: Timestamp) -> ExternResult<()> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            orders_by_month_path(created_at).path_entry_hash()?,
+            LinkTypes::OrdersByMonth,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if link.target.clone().into_action_hash().as_ref() == Some(order_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+    Ok(())
+}
+
+/// Moves the orders linked from the legacy all_orders path to the month and status indexes
+///
+/// Needs to be run once by an order manager, and returns the number of migrated orders
+#[hdk_extern]
+pub fn migrate_all_orders() -> ExternResult<u32> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            legacy_all_orders_path().path_entry_hash()?,
+            LinkTypes::AllOrders,
+        )?
+        .build(),
+    )?;
+    let mut migrated = 0;
+    for link in links {
+        let order_hash =
+            link.target
+                .clone()
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let original_order = get(order_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Order not found".to_string())
+        ))?;
+        let latest_order = get_latest_order(order_hash.clone())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Order not found".to_string())
+        ))?;
+        let latest_order = Order::try_from(latest_order)?;
+
+        index_order_by_month(order_hash.clone(), original_order.action().timestamp())?;
+        index_order_by_status(order_hash, &latest_order.status)?;
+        delete_link(link.create_link_hash)?;
+        migrated += 1;
+    }
+    Ok(migrated)
+}
@@ -1,6 +1,30 @@
 use hdk::prelude::*;
 use orders_integrity::*;
 
+fn offered_products(available_products: &AvailableProducts) -> Vec<ActionHash> {
+    match &available_products.producer_availability {
+        ProducerAvailability::Available { available_products } => available_products.clone(),
+        ProducerAvailability::Unavailable => vec![],
+    }
+}
+
+/// Links the given products to the available products that offer them,
+/// so that the orders in which they were offered can be found from the products
+fn link_offered_products(
+    available_products_hash: &ActionHash,
+    products: Vec<ActionHash>,
+) -> ExternResult<()> {
+    for product_hash in products {
+        create_link(
+            product_hash,
+            available_products_hash.clone(),
+            LinkTypes::ProductToAvailableProducts,
+            (),
+        )?;
+    }
+    Ok(())
+}
+
 #[hdk_extern]
 pub fn create_available_products(
     available_products: AvailableProducts,
@@ -14,6 +38,10 @@ pub fn create_available_products(
         LinkTypes::OrderToAvailableProducts,
         (),
     )?;
+    link_offered_products(
+        &available_products_hash,
+        offered_products(&available_products),
+    )?;
     let record = get(available_products_hash.clone(), GetOptions::default())?
         .ok_or(
             wasm_error!(
@@ -128,6 +156,19 @@ pub struct UpdateAvailableProductsInput {
 pub fn update_available_products(
     input: UpdateAvailableProductsInput,
 ) -> ExternResult<Record> {
+    let previous_record = get(
+            input.previous_available_products_hash.clone(),
+            GetOptions::default(),
+        )?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the previous AvailableProducts"
+                .to_string())
+            ),
+        )?;
+    let previously_offered = offered_products(
+        &AvailableProducts::try_from(previous_record)?,
+    );
     let updated_available_products_hash = update_entry(
         input.previous_available_products_hash.clone(),
         &input.updated_available_products,
@@ -138,6 +179,13 @@ pub fn update_available_products(
         LinkTypes::AvailableProductsUpdates,
         (),
     )?;
+    link_offered_products(
+        &updated_available_products_hash,
+        offered_products(&input.updated_available_products)
+            .into_iter()
+            .filter(|product_hash| !previously_offered.contains(product_hash))
+            .collect(),
+    )?;
     let record = get(updated_available_products_hash.clone(), GetOptions::default())?
         .ok_or(
             wasm_error!(
@@ -0,0 +1,25 @@
+use club_types::ClubSettings;
+use hdk::prelude::*;
+
+/// The part of the DNA properties that holds the settings of the club,
+/// next to the progenitors that the roles zome reads
+#[derive(Serialize, Deserialize, SerializedBytes, Debug)]
+struct ClubProperties {
+    #[serde(default)]
+    club_settings: Option<ClubSettings>,
+}
+
+/// Currency and decimal precision of all the amounts of money in this club
+///
+/// Clubs created before these settings existed don't have them in their DNA properties,
+/// and get the default settings, as do clubs whose settings are out of range
+#[hdk_extern]
+pub fn get_club_settings() -> ExternResult<ClubSettings> {
+    let properties = dna_info()?.modifiers.properties;
+    let club_settings = ClubProperties::try_from(properties)
+        .ok()
+        .and_then(|properties| properties.club_settings)
+        .filter(|club_settings| club_settings.validate().is_ok())
+        .unwrap_or_default();
+    Ok(club_settings)
+}
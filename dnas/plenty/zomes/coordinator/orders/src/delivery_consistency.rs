@@ -0,0 +1,125 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hdk::prelude::*;
+use orders_integrity::*;
+
+use crate::household_order::get_household_orders_in_order;
+use crate::producer_delivery::get_latest_producer_deliveries_for_order;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum DeliveryInconsistency {
+    ProductNotOrdered {
+        producer_delivery_hash: ActionHash,
+        original_product_hash: ActionHash,
+    },
+    HouseholdDidNotOrderProduct {
+        producer_delivery_hash: ActionHash,
+        original_product_hash: ActionHash,
+        household_hash: ActionHash,
+    },
+    DeliveredMoreThanOrdered {
+        producer_delivery_hash: ActionHash,
+        original_product_hash: ActionHash,
+        ordered_amount: u32,
+        delivered_amount: u32,
+    },
+}
+
+#[derive(Default)]
+struct OrderedProduct {
+    amount: u32,
+    households: BTreeSet<ActionHashB64>,
+}
+
+/// Compares the latest revision of every producer delivery in the order against
+/// the household orders, so that mismatches are caught before pickup
+#[hdk_extern]
+pub fn validate_delivery_consistency(
+    order_hash: ActionHash,
+) -> ExternResult<Vec<DeliveryInconsistency>> {
+    let mut ordered_products: BTreeMap<ActionHashB64, OrderedProduct> = BTreeMap::new();
+    for record in get_household_orders_in_order(order_hash.clone())? {
+        let household_order = HouseholdOrder::try_from(record)?;
+        for product_order in household_order.products {
+            let ordered_product = ordered_products
+                .entry(product_order.original_product_hash.into())
+                .or_default();
+            ordered_product.amount += product_order.amount;
+            ordered_product
+                .households
+                .insert(household_order.household_hash.clone().into());
+        }
+    }
+
+    let mut inconsistencies: Vec<DeliveryInconsistency> = Vec::new();
+    for record in get_latest_producer_deliveries_for_order(order_hash)? {
+        let producer_delivery_hash = record.action_address().clone();
+        let producer_delivery = ProducerDelivery::try_from(record)?;
+
+        for (product_hash, product_delivery) in producer_delivery.products {
+            let original_product_hash = ActionHash::from(product_hash.clone());
+            let ProductDelivery::Delivered {
+                delivered_amount, ..
+            } = product_delivery
+            else {
+                continue;
+            };
+            let Some(ordered_product) = ordered_products.get(&product_hash) else {
+                inconsistencies.push(DeliveryInconsistency::ProductNotOrdered {
+                    producer_delivery_hash: producer_delivery_hash.clone(),
+                    original_product_hash,
+                });
+                continue;
+            };
+
+            let households_hashes: Vec<ActionHash> = match &delivered_amount {
+                DeliveredAmount::FixedAmountProduct {
+                    delivered_products, ..
+                } => delivered_products
+                    .iter()
+                    .flat_map(|delivery| delivery.households_hashes.clone())
+                    .collect(),
+                DeliveredAmount::EstimatedAmountProduct {
+                    delivered_products_by_household,
+                    ..
+                } => delivered_products_by_household
+                    .iter()
+                    .flat_map(|delivery| delivery.households_hashes.clone())
+                    .collect(),
+            };
+            for household_hash in households_hashes {
+                if !ordered_product
+                    .households
+                    .contains(&household_hash.clone().into())
+                {
+                    inconsistencies.push(DeliveryInconsistency::HouseholdDidNotOrderProduct {
+                        producer_delivery_hash: producer_delivery_hash.clone(),
+                        original_product_hash: original_product_hash.clone(),
+                        household_hash,
+                    });
+                }
+            }
+
+            if let DeliveredAmount::FixedAmountProduct {
+                delivered_products, ..
+            } = delivered_amount
+            {
+                let delivered_amount: u32 = delivered_products
+                    .iter()
+                    .map(|delivery| delivery.amount)
+                    .sum();
+                if delivered_amount > ordered_product.amount {
+                    inconsistencies.push(DeliveryInconsistency::DeliveredMoreThanOrdered {
+                        producer_delivery_hash: producer_delivery_hash.clone(),
+                        original_product_hash,
+                        ordered_amount: ordered_product.amount,
+                        delivered_amount,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(inconsistencies)
+}
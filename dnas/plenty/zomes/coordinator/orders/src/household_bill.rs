@@ -0,0 +1,227 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use orders_integrity::*;
+use pricing::{price_cents, round_cents, total_weight};
+
+use crate::household_order::get_household_orders_in_order;
+use crate::producer_delivery::get_latest_producer_deliveries_for_order;
+use crate::producer_order_summary::get_product;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HouseholdBillLine {
+    pub original_product_hash: ActionHash,
+    pub ordered_product_hash: ActionHash,
+    pub name: String,
+    pub estimate: bool,
+    pub ordered_amount: u32,
+    /// None while the producer delivery for this product hasn't been processed,
+    /// in which case the line is billed by the ordered amount.
+    /// For estimated products, this is the delivered weight or volume
+    pub delivered_amount: Option<f32>,
+    pub price_cents_per_unit: u32,
+    pub price_changed: bool,
+    pub subtotal_cents: u64,
+    pub margin_cents: u64,
+    pub vat_cents: u64,
+    pub total_cents: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HouseholdBill {
+    pub order_hash: ActionHash,
+    pub household_hash: ActionHash,
+    pub lines: Vec<HouseholdBillLine>,
+    pub subtotal_cents: u64,
+    pub margin_cents: u64,
+    pub vat_cents: u64,
+    pub total_cents: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrderBill {
+    pub order_hash: ActionHash,
+    pub households: Vec<HouseholdBill>,
+    pub total_cents: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ComputeHouseholdBillInput {
+    pub order_hash: ActionHash,
+    pub household_hash: ActionHash,
+}
+
+/// Collects the latest delivery of every product in the order, by original product hash
+fn get_product_deliveries(
+    order_hash: ActionHash,
+) -> ExternResult<BTreeMap<ActionHashB64, ProductDelivery>> {
+    let mut product_deliveries: BTreeMap<ActionHashB64, ProductDelivery> = BTreeMap::new();
+    for record in get_latest_producer_deliveries_for_order(order_hash)? {
+        let producer_delivery = ProducerDelivery::try_from(record)?;
+        product_deliveries.extend(producer_delivery.products);
+    }
+    Ok(product_deliveries)
+}
+
+/// Returns the amount delivered to the given household, and the changed price if any
+///
+/// Deliveries shared between several households are split evenly among them
+pub fn delivered_to_household(
+    product_delivery: &ProductDelivery,
+    household_hash: &ActionHash,
+) -> (f32, Option<u32>) {
+    let ProductDelivery::Delivered {
+        delivered_amount, ..
+    } = product_delivery
+    else {
+        return (0.0, None);
+    };
+    match delivered_amount {
+        DeliveredAmount::FixedAmountProduct {
+            delivered_products,
+            price_cents_per_unit_changed,
+        } => {
+            let amount = delivered_products
+                .iter()
+                .filter(|delivery| delivery.households_hashes.contains(household_hash))
+                .map(|delivery| delivery.amount as f32 / delivery.households_hashes.len() as f32)
+                .sum();
+            (amount, *price_cents_per_unit_changed)
+        }
+        DeliveredAmount::EstimatedAmountProduct {
+            delivered_products_by_household,
+            price_cents_per_unit_changed,
+        } => {
+            let amount = delivered_products_by_household
+                .iter()
+                .filter(|delivery| delivery.households_hashes.contains(household_hash))
+                .map(|delivery| {
+                    total_weight(&delivery.products) / delivery.households_hashes.len() as f32
+                })
+                .sum();
+            (amount, *price_cents_per_unit_changed)
+        }
+    }
+}
+
+fn compute_bill_line(
+    product_order: ProductOrder,
+    product_deliveries: &BTreeMap<ActionHashB64, ProductDelivery>,
+    household_hash: &ActionHash,
+) -> ExternResult<HouseholdBillLine> {
+    let product = get_product(product_order.ordered_product_hash.clone())?;
+    let estimate = product.packaging.estimate;
+
+    let (delivered_amount, price_cents_per_unit_changed) = match product_deliveries
+        .get(&product_order.original_product_hash.clone().into())
+    {
+        Some(product_delivery) => {
+            let (amount, price_changed) = delivered_to_household(product_delivery, household_hash);
+            (Some(amount), price_changed)
+        }
+        None => (None, None),
+    };
+    let price_cents_per_unit = price_cents_per_unit_changed.unwrap_or(product.price_cents);
+
+    // Estimated products are priced per package, but delivered by weight or volume
+    let subtotal = match delivered_amount {
+        Some(delivered_amount) => price_cents(
+            estimate,
+            product.packaging.amount_per_package,
+            price_cents_per_unit,
+            delivered_amount,
+        ),
+        None => product_order.amount as f64 * price_cents_per_unit as f64,
+    };
+    let margin = subtotal * product.margin_percentage.unwrap_or(0.0) as f64 / 100.0;
+    let vat = (subtotal + margin) * product.vat_percentage as f64 / 100.0;
+
+    let subtotal_cents = round_cents(subtotal);
+    let margin_cents = round_cents(margin);
+    let vat_cents = round_cents(vat);
+
+    Ok(HouseholdBillLine {
+        original_product_hash: product_order.original_product_hash,
+        ordered_product_hash: product_order.ordered_product_hash,
+        name: product.name,
+        estimate,
+        ordered_amount: product_order.amount,
+        delivered_amount,
+        price_cents_per_unit,
+        price_changed: price_cents_per_unit_changed.is_some(),
+        subtotal_cents,
+        margin_cents,
+        vat_cents,
+        total_cents: subtotal_cents + margin_cents + vat_cents,
+    })
+}
+
+fn compute_bill(
+    order_hash: ActionHash,
+    household_hash: ActionHash,
+    products: Vec<ProductOrder>,
+    product_deliveries: &BTreeMap<ActionHashB64, ProductDelivery>,
+) -> ExternResult<HouseholdBill> {
+    let lines = products
+        .into_iter()
+        .map(|product_order| compute_bill_line(product_order, product_deliveries, &household_hash))
+        .collect::<ExternResult<Vec<HouseholdBillLine>>>()?;
+
+    Ok(HouseholdBill {
+        order_hash,
+        household_hash,
+        subtotal_cents: lines.iter().map(|line| line.subtotal_cents).sum(),
+        margin_cents: lines.iter().map(|line| line.margin_cents).sum(),
+        vat_cents: lines.iter().map(|line| line.vat_cents).sum(),
+        total_cents: lines.iter().map(|line| line.total_cents).sum(),
+        lines,
+    })
+}
+
+/// Itemizes what the household owes for the given order,
+/// with the delivered amounts and changed prices from the producer deliveries,
+/// plus the margin and the VAT on top of it
+#[hdk_extern]
+pub fn compute_household_bill(input: ComputeHouseholdBillInput) -> ExternResult<HouseholdBill> {
+    let household_orders = get_household_orders_in_order(input.order_hash.clone())?;
+    let product_deliveries = get_product_deliveries(input.order_hash.clone())?;
+
+    let mut products: Vec<ProductOrder> = Vec::new();
+    for record in household_orders {
+        let household_order = HouseholdOrder::try_from(record)?;
+        if household_order.household_hash.eq(&input.household_hash) {
+            products.extend(household_order.products);
+        }
+    }
+
+    compute_bill(
+        input.order_hash,
+        input.household_hash,
+        products,
+        &product_deliveries,
+    )
+}
+
+/// Bill table for bookkeepers, with the bill of every household that ordered in the given order
+#[hdk_extern]
+pub fn compute_order_bill(order_hash: ActionHash) -> ExternResult<OrderBill> {
+    let household_orders = get_household_orders_in_order(order_hash.clone())?;
+    let product_deliveries = get_product_deliveries(order_hash.clone())?;
+
+    let mut households = Vec::new();
+    for record in household_orders {
+        let household_order = HouseholdOrder::try_from(record)?;
+        households.push(compute_bill(
+            order_hash.clone(),
+            household_order.household_hash,
+            household_order.products,
+            &product_deliveries,
+        )?);
+    }
+
+    Ok(OrderBill {
+        order_hash,
+        total_cents: households.iter().map(|bill| bill.total_cents).sum(),
+        households,
+    })
+}
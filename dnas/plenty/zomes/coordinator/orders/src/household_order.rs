@@ -1,8 +1,23 @@
 use hdk::prelude::*;
 use orders_integrity::*;
 
+use crate::order::{get_all_revisions_for_order, get_latest_order};
+use crate::product_availability::check_household_order_availability;
+
+/// Validation checks the order status against `latest_order_hash`,
+/// so always commit household orders against the latest revision of their order
+fn with_latest_order_hash(mut household_order: HouseholdOrder) -> ExternResult<HouseholdOrder> {
+    let latest_order = get_latest_order(household_order.order_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Order not found".to_string())
+    ))?;
+    household_order.latest_order_hash = latest_order.action_address().clone();
+    Ok(household_order)
+}
+
 #[hdk_extern]
 pub fn create_household_order(household_order: HouseholdOrder) -> ExternResult<Record> {
+    let household_order = with_latest_order_hash(household_order)?;
+    check_household_order_availability(&household_order)?;
     let household_order_hash = create_entry(&EntryTypes::HouseholdOrder(household_order.clone()))?;
     create_link(
         household_order.order_hash.clone(),
@@ -110,9 +125,11 @@ pub struct UpdateHouseholdOrderInput {
 
 #[hdk_extern]
 pub fn update_household_order(input: UpdateHouseholdOrderInput) -> ExternResult<Record> {
+    let updated_household_order = with_latest_order_hash(input.updated_household_order)?;
+    check_household_order_availability(&updated_household_order)?;
     let updated_household_order_hash = update_entry(
         input.previous_household_order_hash.clone(),
-        &input.updated_household_order,
+        &updated_household_order,
     )?;
     create_link(
         input.original_household_order_hash.clone(),
@@ -240,3 +257,88 @@ pub fn get_deleted_household_orders_for_order(
         .filter(|(_link, deletes)| !deletes.is_empty())
         .collect())
 }
+
+/// Returns the latest revision of every household order linked from the given order,
+/// skipping the deleted ones
+pub fn get_live_household_orders_for_order(order_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    let links = get_household_orders_for_order(order_hash)?;
+    let mut household_orders: Vec<Record> = Vec::new();
+    for link in links {
+        let original_household_order_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let deletes = get_all_deletes_for_household_order(original_household_order_hash.clone())?;
+        if deletes.map(|deletes| !deletes.is_empty()).unwrap_or(true) {
+            continue;
+        }
+        if let Some(latest_household_order) =
+            get_latest_household_order(original_household_order_hash)?
+        {
+            household_orders.push(latest_household_order);
+        }
+    }
+    Ok(household_orders)
+}
+
+/// Returns the household orders that closing the order snapshots: for every live household order,
+/// its latest revision committed before the deadline or by an order manager, since validation
+/// rejects Closed orders with any other revision
+pub fn get_household_orders_to_close(
+    order_hash: ActionHash,
+    deadline: Timestamp,
+) -> ExternResult<Vec<ActionHash>> {
+    let links = get_household_orders_for_order(order_hash)?;
+    let mut household_orders: Vec<ActionHash> = Vec::new();
+    for link in links {
+        let original_household_order_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let deletes = get_all_deletes_for_household_order(original_household_order_hash.clone())?;
+        if deletes.map(|deletes| !deletes.is_empty()).unwrap_or(true) {
+            continue;
+        }
+        let mut revisions = get_all_revisions_for_household_order(original_household_order_hash)?;
+        revisions.sort_by_key(|record| record.action().timestamp());
+        for revision in revisions.into_iter().rev() {
+            if is_household_order_within_deadline(revision.action_address(), &deadline)? {
+                household_orders.push(revision.action_address().clone());
+                break;
+            }
+        }
+    }
+    Ok(household_orders)
+}
+
+/// Returns the household orders that make up the given order:
+/// the live ones while the order is still being prepared or open,
+/// and the snapshot taken in its latest Closed revision afterwards
+pub fn get_household_orders_in_order(order_hash: ActionHash) -> ExternResult<Vec<Record>> {
+    let mut revisions = get_all_revisions_for_order(order_hash.clone())?;
+    revisions.sort_by_key(|record| record.action().timestamp());
+    let Some(latest_revision) = revisions.last() else {
+        return Ok(vec![]);
+    };
+    let latest_order = Order::try_from(latest_revision.clone())?;
+    if let OrderStatus::Preparing | OrderStatus::Open { .. } = latest_order.status {
+        return get_live_household_orders_for_order(order_hash);
+    }
+
+    for revision in revisions.into_iter().rev() {
+        let order = Order::try_from(revision)?;
+        if let OrderStatus::Closed { household_orders } = order.status {
+            let get_input: Vec<GetInput> = household_orders
+                .into_iter()
+                .map(|hash| GetInput::new(hash.into(), GetOptions::default()))
+                .collect();
+            let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+            return Ok(records.into_iter().flatten().collect());
+        }
+    }
+    Ok(vec![])
+}
@@ -0,0 +1,217 @@
+use hdk::prelude::*;
+use orders_integrity::*;
+
+#[hdk_extern]
+pub fn create_household_payment(household_payment: HouseholdPayment) -> ExternResult<Record> {
+    let household_payment_hash =
+        create_entry(&EntryTypes::HouseholdPayment(household_payment.clone()))?;
+    create_link(
+        household_payment.order_hash.clone(),
+        household_payment_hash.clone(),
+        LinkTypes::OrderToHouseholdPayments,
+        (),
+    )?;
+    create_link(
+        household_payment.household_hash.clone(),
+        household_payment_hash.clone(),
+        LinkTypes::HouseholdToHouseholdPayments,
+        (),
+    )?;
+    let record = get(household_payment_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created HouseholdPayment".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_original_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let Some(details) = get_details(original_household_payment_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    match details {
+        Details::Record(details) => Ok(Some(details.record)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed get details response".to_string()
+        ))),
+    }
+}
+
+#[hdk_extern]
+pub fn get_latest_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let Some(details) = get_details(original_household_payment_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    let record_details = match details {
+        Details::Entry(_) => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed details".into()
+        ))),
+        Details::Record(record_details) => Ok(record_details),
+    }?;
+    match record_details.updates.last() {
+        Some(update) => get_latest_household_payment(update.action_address().clone()),
+        None => Ok(Some(record_details.record)),
+    }
+}
+
+#[hdk_extern]
+pub fn get_all_revisions_for_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<Vec<Record>> {
+    let Some(Details::Record(details)) =
+        get_details(original_household_payment_hash, GetOptions::default())?
+    else {
+        return Ok(vec![]);
+    };
+    let mut records = vec![details.record];
+    for update in details.updates {
+        let mut update_records =
+            get_all_revisions_for_household_payment(update.action_address().clone())?;
+        records.append(&mut update_records);
+    }
+    Ok(records)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateHouseholdPaymentInput {
+    pub previous_household_payment_hash: ActionHash,
+    pub updated_household_payment: HouseholdPayment,
+}
+
+#[hdk_extern]
+pub fn update_household_payment(input: UpdateHouseholdPaymentInput) -> ExternResult<Record> {
+    let updated_household_payment_hash = update_entry(
+        input.previous_household_payment_hash,
+        &input.updated_household_payment,
+    )?;
+    let record = get(
+        updated_household_payment_hash.clone(),
+        GetOptions::default(),
+    )?
+    .ok_or(wasm_error!(WasmErrorInner::Guest(
+        "Could not find the newly updated HouseholdPayment".to_string()
+    )))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn delete_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<ActionHash> {
+    let details = get_details(
+        original_household_payment_hash.clone(),
+        GetOptions::default(),
+    )?
+    .ok_or(wasm_error!(WasmErrorInner::Guest(
+        "HouseholdPayment not found".to_string()
+    )))?;
+    let record = match details {
+        Details::Record(details) => Ok(details.record),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed get details response".to_string()
+        ))),
+    }?;
+    let entry = record
+        .entry()
+        .as_option()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "HouseholdPayment record has no entry".to_string()
+        )))?;
+    let household_payment = <HouseholdPayment>::try_from(entry)?;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            household_payment.order_hash.clone(),
+            LinkTypes::OrderToHouseholdPayments,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if let Some(action_hash) = link.target.into_action_hash() {
+            if action_hash == original_household_payment_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            household_payment.household_hash.clone(),
+            LinkTypes::HouseholdToHouseholdPayments,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if let Some(action_hash) = link.target.into_action_hash() {
+            if action_hash == original_household_payment_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    delete_entry(original_household_payment_hash)
+}
+
+#[hdk_extern]
+pub fn get_all_deletes_for_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<Option<Vec<SignedActionHashed>>> {
+    let Some(details) = get_details(original_household_payment_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    match details {
+        Details::Entry(_) => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed details".into()
+        ))),
+        Details::Record(record_details) => Ok(Some(record_details.deletes)),
+    }
+}
+
+#[hdk_extern]
+pub fn get_oldest_delete_for_household_payment(
+    original_household_payment_hash: ActionHash,
+) -> ExternResult<Option<SignedActionHashed>> {
+    let Some(mut deletes) = get_all_deletes_for_household_payment(original_household_payment_hash)?
+    else {
+        return Ok(None);
+    };
+    deletes.sort_by(|delete_a, delete_b| {
+        delete_a
+            .action()
+            .timestamp()
+            .cmp(&delete_b.action().timestamp())
+    });
+    Ok(deletes.first().cloned())
+}
+
+#[hdk_extern]
+pub fn get_household_payments_for_order(order_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(order_hash, LinkTypes::OrderToHouseholdPayments)?.build(),
+    )
+}
+
+#[hdk_extern]
+pub fn get_household_payments_for_household(household_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(household_hash, LinkTypes::HouseholdToHouseholdPayments)?
+            .build(),
+    )
+}
+
+#[hdk_extern]
+pub fn get_deleted_household_payments_for_order(
+    order_hash: ActionHash,
+) -> ExternResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
+    let details = get_link_details(
+        order_hash,
+        LinkTypes::OrderToHouseholdPayments,
+        None,
+        GetOptions::default(),
+    )?;
+    Ok(details
+        .into_inner()
+        .into_iter()
+        .filter(|(_link, deletes)| !deletes.is_empty())
+        .collect())
+}
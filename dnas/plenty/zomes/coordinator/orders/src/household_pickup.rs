@@ -0,0 +1,214 @@
+use hdk::prelude::*;
+use orders_integrity::*;
+
+#[hdk_extern]
+pub fn create_household_pickup(household_pickup: HouseholdPickup) -> ExternResult<Record> {
+    let household_pickup_hash =
+        create_entry(&EntryTypes::HouseholdPickup(household_pickup.clone()))?;
+    create_link(
+        household_pickup.order_hash.clone(),
+        household_pickup_hash.clone(),
+        LinkTypes::OrderToHouseholdPickups,
+        (),
+    )?;
+    create_link(
+        household_pickup.household_hash.clone(),
+        household_pickup_hash.clone(),
+        LinkTypes::HouseholdToHouseholdPickups,
+        (),
+    )?;
+    let record = get(household_pickup_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created HouseholdPickup".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_original_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let Some(details) = get_details(original_household_pickup_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    match details {
+        Details::Record(details) => Ok(Some(details.record)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed get details response".to_string()
+        ))),
+    }
+}
+
+#[hdk_extern]
+pub fn get_latest_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let Some(details) = get_details(original_household_pickup_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    let record_details = match details {
+        Details::Entry(_) => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed details".into()
+        ))),
+        Details::Record(record_details) => Ok(record_details),
+    }?;
+    match record_details.updates.last() {
+        Some(update) => get_latest_household_pickup(update.action_address().clone()),
+        None => Ok(Some(record_details.record)),
+    }
+}
+
+#[hdk_extern]
+pub fn get_all_revisions_for_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<Vec<Record>> {
+    let Some(Details::Record(details)) =
+        get_details(original_household_pickup_hash, GetOptions::default())?
+    else {
+        return Ok(vec![]);
+    };
+    let mut records = vec![details.record];
+    for update in details.updates {
+        let mut update_records =
+            get_all_revisions_for_household_pickup(update.action_address().clone())?;
+        records.append(&mut update_records);
+    }
+    Ok(records)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateHouseholdPickupInput {
+    pub previous_household_pickup_hash: ActionHash,
+    pub updated_household_pickup: HouseholdPickup,
+}
+
+#[hdk_extern]
+pub fn update_household_pickup(input: UpdateHouseholdPickupInput) -> ExternResult<Record> {
+    let updated_household_pickup_hash = update_entry(
+        input.previous_household_pickup_hash,
+        &input.updated_household_pickup,
+    )?;
+    let record =
+        get(updated_household_pickup_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Could not find the newly updated HouseholdPickup".to_string())
+        ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn delete_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<ActionHash> {
+    let details = get_details(
+        original_household_pickup_hash.clone(),
+        GetOptions::default(),
+    )?
+    .ok_or(wasm_error!(WasmErrorInner::Guest(
+        "HouseholdPickup not found".to_string()
+    )))?;
+    let record = match details {
+        Details::Record(details) => Ok(details.record),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed get details response".to_string()
+        ))),
+    }?;
+    let entry = record
+        .entry()
+        .as_option()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "HouseholdPickup record has no entry".to_string()
+        )))?;
+    let household_pickup = <HouseholdPickup>::try_from(entry)?;
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            household_pickup.order_hash.clone(),
+            LinkTypes::OrderToHouseholdPickups,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if let Some(action_hash) = link.target.into_action_hash() {
+            if action_hash == original_household_pickup_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            household_pickup.household_hash.clone(),
+            LinkTypes::HouseholdToHouseholdPickups,
+        )?
+        .build(),
+    )?;
+    for link in links {
+        if let Some(action_hash) = link.target.into_action_hash() {
+            if action_hash == original_household_pickup_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    delete_entry(original_household_pickup_hash)
+}
+
+#[hdk_extern]
+pub fn get_all_deletes_for_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<Option<Vec<SignedActionHashed>>> {
+    let Some(details) = get_details(original_household_pickup_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    match details {
+        Details::Entry(_) => Err(wasm_error!(WasmErrorInner::Guest(
+            "Malformed details".into()
+        ))),
+        Details::Record(record_details) => Ok(Some(record_details.deletes)),
+    }
+}
+
+#[hdk_extern]
+pub fn get_oldest_delete_for_household_pickup(
+    original_household_pickup_hash: ActionHash,
+) -> ExternResult<Option<SignedActionHashed>> {
+    let Some(mut deletes) = get_all_deletes_for_household_pickup(original_household_pickup_hash)?
+    else {
+        return Ok(None);
+    };
+    deletes.sort_by(|delete_a, delete_b| {
+        delete_a
+            .action()
+            .timestamp()
+            .cmp(&delete_b.action().timestamp())
+    });
+    Ok(deletes.first().cloned())
+}
+
+#[hdk_extern]
+pub fn get_household_pickups_for_order(order_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(order_hash, LinkTypes::OrderToHouseholdPickups)?.build(),
+    )
+}
+
+#[hdk_extern]
+pub fn get_household_pickups_for_household(household_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(household_hash, LinkTypes::HouseholdToHouseholdPickups)?
+            .build(),
+    )
+}
+
+#[hdk_extern]
+pub fn get_deleted_household_pickups_for_order(
+    order_hash: ActionHash,
+) -> ExternResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
+    let details = get_link_details(
+        order_hash,
+        LinkTypes::OrderToHouseholdPickups,
+        None,
+        GetOptions::default(),
+    )?;
+    Ok(details
+        .into_inner()
+        .into_iter()
+        .filter(|(_link, deletes)| !deletes.is_empty())
+        .collect())
+}
@@ -0,0 +1,34 @@
+use hdk::prelude::*;
+
+use crate::call_local_zome;
+
+pub const HOUSEHOLDS_ZOME_NAME: &str = "households";
+
+pub fn get_households_for_member(member: AgentPubKey) -> ExternResult<Vec<Link>> {
+    call_local_zome(HOUSEHOLDS_ZOME_NAME, "get_households_for_member", member)
+}
+
+pub fn get_latest_household(original_household_hash: ActionHash) -> ExternResult<Option<Record>> {
+    call_local_zome(
+        HOUSEHOLDS_ZOME_NAME,
+        "get_latest_household",
+        original_household_hash,
+    )
+}
+
+/// Returns the household the calling agent joined most recently
+pub fn get_my_household_hash() -> ExternResult<ActionHash> {
+    let links = get_households_for_member(agent_info()?.agent_initial_pubkey)?;
+    let latest_link = links
+        .into_iter()
+        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp))
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "You are not a member of any household".to_string()
+        )))?;
+    latest_link
+        .target
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))
+}
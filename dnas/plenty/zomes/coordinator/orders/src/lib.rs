@@ -4,6 +4,21 @@ pub mod producer_invoice;
 pub mod producer_delivery;
 pub mod household_order;
 pub mod order;
+pub mod household_payment;
+pub mod producer_order_summary;
+pub mod producers;
+pub mod product_availability;
+pub mod household_bill;
+pub mod delivery_consistency;
+pub mod order_template;
+pub mod households;
+pub mod standing_order;
+pub mod order_overview;
+pub mod reconciliation;
+pub mod packing_list;
+pub mod household_pickup;
+pub mod club_settings;
+pub mod product_orders;
 use hdk::prelude::*;
 use orders_integrity::*;
 
@@ -12,6 +27,26 @@ pub fn init() -> ExternResult<InitCallbackResult> {
     Ok(InitCallbackResult::Pass)
 }
 
+pub fn call_local_zome<I, O>(zome_name: &str, fn_name: &str, payload: I) -> ExternResult<O>
+where
+    I: Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from(zome_name),
+        FunctionName::from(fn_name),
+        None,
+        payload,
+    )?;
+    match response {
+        ZomeCallResponse::Ok(result) => result.decode().map_err(|e| wasm_error!(e)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Error calling {fn_name} in the {zome_name} zome: {response:?}"
+        )))),
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(tag = "type")]
 pub enum Signal {
@@ -28,14 +63,21 @@ pub enum Signal {
         original_app_entry: EntryTypes,
     },
     EntryDeleted { action: SignedActionHashed, original_app_entry: EntryTypes },
+    ProductsOversubscribed {
+        order_hash: ActionHash,
+        products: Vec<product_availability::ProductAvailability>,
+    },
 }
 
 #[hdk_extern(infallible)]
 pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
     for action in committed_actions {
-        if let Err(err) = signal_action(action) {
+        if let Err(err) = signal_action(action.clone()) {
             error!("Error signaling new action: {:?}", err);
         }
+        if let Err(err) = check_oversubscription(action) {
+            error!("Error checking the availability of the ordered products: {:?}", err);
+        }
     }
 }
 
@@ -125,6 +167,18 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
     }
 }
 
+fn check_oversubscription(action: SignedActionHashed) -> ExternResult<()> {
+    if !matches!(action.action(), Action::Create(_) | Action::Update(_)) {
+        return Ok(());
+    }
+    if let Some(EntryTypes::HouseholdOrder(household_order)) = get_entry_for_action(
+        &action.hashed.hash,
+    )? {
+        product_availability::signal_oversubscribed_products(household_order.order_hash)?;
+    }
+    Ok(())
+}
+
 fn get_entry_for_action(action_hash: &ActionHash) -> ExternResult<Option<EntryTypes>> {
     let record = match get_details(action_hash.clone(), GetOptions::default())? {
         Some(Details::Record(record_details)) => record_details.record,
@@ -1,6 +1,12 @@
 use hdk::prelude::*;
 use orders_integrity::*;
 
+use crate::all_orders::{
+    index_order_by_month, index_order_by_status, unindex_order_by_month,
+    unindex_order_by_status,
+};
+use crate::household_order::get_household_orders_to_close;
+
 #[hdk_extern]
 pub fn create_order(order: Order) -> ExternResult<Record> {
     let order_hash = create_entry(&EntryTypes::Order(order.clone()))?;
@@ -11,8 +17,8 @@ pub fn create_order(order: Order) -> ExternResult<Record> {
                 .to_string())
             ),
         )?;
-    let path = Path::from("all_orders");
-    create_link(path.path_entry_hash()?, order_hash.clone(), LinkTypes::AllOrders, ())?;
+    index_order_by_month(order_hash.clone(), record.action().timestamp())?;
+    index_order_by_status(order_hash, &order.status)?;
     Ok(record)
 }
 
@@ -113,6 +119,9 @@ pub struct UpdateOrderInput {
 
 #[hdk_extern]
 pub fn update_order(input: UpdateOrderInput) -> ExternResult<Record> {
+    let previous_record = get(input.previous_order_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Order not found".to_string())))?;
+    let previous_order = Order::try_from(previous_record)?;
     let updated_order_hash = update_entry(
         input.previous_order_hash.clone(),
         &input.updated_order,
@@ -130,11 +139,47 @@ pub fn update_order(input: UpdateOrderInput) -> ExternResult<Record> {
                 .to_string())
             ),
         )?;
+    if previous_order.status.name() != input.updated_order.status.name() {
+        unindex_order_by_status(&input.original_order_hash)?;
+        index_order_by_status(input.original_order_hash, &input.updated_order.status)?;
+    }
     Ok(record)
 }
 
+#[hdk_extern]
+pub fn close_order(order_hash: ActionHash) -> ExternResult<Record> {
+    let latest_order_record = get_latest_order(order_hash.clone())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Order not found".to_string())))?;
+    let latest_order = Order::try_from(latest_order_record.clone())?;
+    let OrderStatus::Open { deadline, .. } = latest_order.status else {
+        return Err(
+            wasm_error!(WasmErrorInner::Guest("Only Open orders can be closed".to_string())),
+        );
+    };
+    if sys_time()?.lt(&deadline) {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Orders can only be closed after their deadline".to_string()
+        )));
+    }
+
+    let household_orders = get_household_orders_to_close(order_hash.clone(), deadline)?;
+
+    update_order(UpdateOrderInput {
+        original_order_hash: order_hash,
+        previous_order_hash: latest_order_record.action_address().clone(),
+        updated_order: Order {
+            name: latest_order.name,
+            status: OrderStatus::Closed { household_orders },
+        },
+    })
+}
+
 #[hdk_extern]
 pub fn delete_order(original_order_hash: ActionHash) -> ExternResult<ActionHash> {
+    let record = get(original_order_hash.clone(), GetOptions::default())?
+        .ok_or(wasm_error!(WasmErrorInner::Guest("Order not found".to_string())))?;
+    unindex_order_by_month(&original_order_hash, record.action().timestamp())?;
+    unindex_order_by_status(&original_order_hash)?;
     let path = Path::from("all_orders");
     let links = get_links(
         GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllOrders)?
@@ -0,0 +1,134 @@
+use hdk::prelude::*;
+use orders_integrity::*;
+
+use crate::available_products::get_available_products_for_order;
+use crate::household_order::get_household_orders_for_order;
+use crate::order::get_latest_order;
+use crate::producer_delivery::get_producer_deliveries_for_order;
+use crate::producer_invoice::get_producer_invoices_for_order;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OrderOverview {
+    pub order: Record,
+    pub household_orders: Vec<Record>,
+    pub available_products: Vec<Record>,
+    pub producer_deliveries: Vec<Record>,
+    pub producer_invoices: Vec<Record>,
+}
+
+fn link_targets(links: Vec<Link>) -> ExternResult<Vec<ActionHash>> {
+    links
+        .into_iter()
+        .map(|link| {
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))
+        })
+        .collect()
+}
+
+fn get_records(action_hashes: Vec<ActionHash>) -> ExternResult<Vec<Record>> {
+    let get_input: Vec<GetInput> = action_hashes
+        .into_iter()
+        .map(|action_hash| GetInput::new(action_hash.into(), GetOptions::default()))
+        .collect();
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    Ok(records.into_iter().flatten().collect())
+}
+
+/// Latest revisions for entries whose updates are tracked with links from the original action,
+/// with one batched get_links and one batched get for all of them
+fn get_latest_by_update_links(
+    original_hashes: Vec<ActionHash>,
+    link_type: LinkTypes,
+) -> ExternResult<Vec<Record>> {
+    let get_links_input: Vec<GetLinksInput> = original_hashes
+        .iter()
+        .map(|original_hash| {
+            Ok(GetLinksInputBuilder::try_new(original_hash.clone(), link_type)?.build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let updates_links = HDK.with(|hdk| hdk.borrow().get_links(get_links_input))?;
+
+    let latest_hashes = original_hashes
+        .into_iter()
+        .zip(updates_links)
+        .map(|(original_hash, links)| {
+            let latest_link = links
+                .into_iter()
+                .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
+            match latest_link {
+                Some(link) => {
+                    link.target
+                        .into_action_hash()
+                        .ok_or(wasm_error!(WasmErrorInner::Guest(
+                            "No action hash associated with link".to_string()
+                        )))
+                }
+                None => Ok(original_hash),
+            }
+        })
+        .collect::<ExternResult<Vec<ActionHash>>>()?;
+    get_records(latest_hashes)
+}
+
+/// Latest revisions for entries whose updates are followed through get_details,
+/// with one batched get_details per level of updates
+fn get_latest_by_details(original_hashes: Vec<ActionHash>) -> ExternResult<Vec<Record>> {
+    let mut latest_records: Vec<Record> = Vec::new();
+    let mut pending = original_hashes;
+    while !pending.is_empty() {
+        let get_input: Vec<GetInput> = pending
+            .into_iter()
+            .map(|action_hash| GetInput::new(action_hash.into(), GetOptions::default()))
+            .collect();
+        let details = HDK.with(|hdk| hdk.borrow().get_details(get_input))?;
+        pending = Vec::new();
+        for details in details.into_iter().flatten() {
+            let Details::Record(record_details) = details else {
+                return Err(wasm_error!(WasmErrorInner::Guest(
+                    "Malformed details".into()
+                )));
+            };
+            match record_details.updates.last() {
+                Some(update) => pending.push(update.action_address().clone()),
+                None => latest_records.push(record_details.record),
+            }
+        }
+    }
+    Ok(latest_records)
+}
+
+/// Everything needed to render an order in one call: the latest revision of the order
+/// and of every household order, available products, producer delivery and producer invoice
+/// linked from it
+#[hdk_extern]
+pub fn get_order_overview(order_hash: ActionHash) -> ExternResult<Option<OrderOverview>> {
+    let Some(order) = get_latest_order(order_hash.clone())? else {
+        return Ok(None);
+    };
+
+    let household_orders = get_latest_by_update_links(
+        link_targets(get_household_orders_for_order(order_hash.clone())?)?,
+        LinkTypes::HouseholdOrderUpdates,
+    )?;
+    let available_products = get_latest_by_update_links(
+        link_targets(get_available_products_for_order(order_hash.clone())?)?,
+        LinkTypes::AvailableProductsUpdates,
+    )?;
+    let producer_deliveries = get_latest_by_details(link_targets(
+        get_producer_deliveries_for_order(order_hash.clone())?,
+    )?)?;
+    let producer_invoices =
+        get_latest_by_details(link_targets(get_producer_invoices_for_order(order_hash)?)?)?;
+
+    Ok(Some(OrderOverview {
+        order,
+        household_orders,
+        available_products,
+        producer_deliveries,
+        producer_invoices,
+    }))
+}
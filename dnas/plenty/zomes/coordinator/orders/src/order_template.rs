@@ -0,0 +1,112 @@
+use hdk::prelude::*;
+use orders_integrity::*;
+
+use crate::available_products::{
+    create_available_products, get_available_products_for_order, get_latest_available_products,
+};
+use crate::order::create_order;
+use crate::producers::{
+    get_all_deletes_for_producer, get_all_deletes_for_product, get_latest_producer,
+    get_latest_product,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CreateOrderFromTemplateInput {
+    pub previous_order_hash: ActionHash,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct OrderFromTemplate {
+    pub order: Record,
+    pub available_products: Vec<Record>,
+    /// Original hashes of the products offered in the template order that have been deleted since
+    pub deleted_products: Vec<ActionHash>,
+    /// Original hashes of the producers in the template order that have been deleted since
+    pub deleted_producers: Vec<ActionHash>,
+}
+
+fn is_deleted(deletes: Option<Vec<SignedActionHashed>>) -> bool {
+    deletes.map(|deletes| !deletes.is_empty()).unwrap_or(true)
+}
+
+/// Creates a new Preparing order with the same availability as the given order,
+/// pointing every AvailableProducts to the current revision of its producer
+///
+/// `available_products` hold original product hashes, so they don't need to be bumped:
+/// the products that were deleted are dropped and reported instead
+#[hdk_extern]
+pub fn create_order_from_template(
+    input: CreateOrderFromTemplateInput,
+) -> ExternResult<OrderFromTemplate> {
+    let order = create_order(Order {
+        name: input.name,
+        status: OrderStatus::Preparing,
+    })?;
+    let order_hash = order.action_address().clone();
+
+    let mut available_products: Vec<Record> = Vec::new();
+    let mut deleted_products: Vec<ActionHash> = Vec::new();
+    let mut deleted_producers: Vec<ActionHash> = Vec::new();
+
+    for link in get_available_products_for_order(input.previous_order_hash)? {
+        let original_available_products_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let Some(record) = get_latest_available_products(original_available_products_hash)? else {
+            continue;
+        };
+        let template = AvailableProducts::try_from(record)?;
+
+        let latest_producer = if is_deleted(get_all_deletes_for_producer(
+            template.original_producer_hash.clone(),
+        )?) {
+            None
+        } else {
+            get_latest_producer(template.original_producer_hash.clone())?
+        };
+        let Some(latest_producer) = latest_producer else {
+            deleted_producers.push(template.original_producer_hash);
+            continue;
+        };
+
+        let producer_availability = match template.producer_availability {
+            ProducerAvailability::Available {
+                available_products: products,
+            } => {
+                let mut live_products: Vec<ActionHash> = Vec::new();
+                for original_product_hash in products {
+                    let deleted =
+                        is_deleted(get_all_deletes_for_product(original_product_hash.clone())?)
+                            || get_latest_product(original_product_hash.clone())?.is_none();
+                    if deleted {
+                        deleted_products.push(original_product_hash);
+                    } else {
+                        live_products.push(original_product_hash);
+                    }
+                }
+                ProducerAvailability::Available {
+                    available_products: live_products,
+                }
+            }
+            ProducerAvailability::Unavailable => ProducerAvailability::Unavailable,
+        };
+
+        available_products.push(create_available_products(AvailableProducts {
+            order_hash: order_hash.clone(),
+            original_producer_hash: template.original_producer_hash,
+            latest_producer_hash: latest_producer.action_address().clone(),
+            producer_availability,
+        })?);
+    }
+
+    Ok(OrderFromTemplate {
+        order,
+        available_products,
+        deleted_products,
+        deleted_producers,
+    })
+}
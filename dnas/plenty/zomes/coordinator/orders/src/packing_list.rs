@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use households_types::Household;
+use orders_integrity::*;
+use producers_types::{Packaging, Producer, Product};
+
+use crate::household_bill::delivered_to_household;
+use crate::household_order::get_household_orders_in_order;
+use crate::households::get_latest_household;
+use crate::producer_delivery::get_latest_producer_deliveries_for_order;
+use crate::producer_invoice::delivered_quantity;
+use crate::producer_order_summary::get_product;
+use crate::producers::get_latest_producer;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PackingListLine {
+    pub original_product_hash: ActionHash,
+    pub product_name: String,
+    pub packaging: Packaging,
+    pub ordered_amount: u32,
+    /// None while the producer delivery for this product hasn't been recorded.
+    /// For estimated products, this is the delivered weight or volume
+    pub delivered_amount: Option<f32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HouseholdPackingList {
+    pub household_hash: ActionHash,
+    pub household_name: String,
+    pub lines: Vec<PackingListLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProducerPickingList {
+    pub producer_hash: ActionHash,
+    pub producer_name: String,
+    /// What was ordered from the producer, added up across all households
+    pub lines: Vec<PackingListLine>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PackingList {
+    pub order_hash: ActionHash,
+    pub households: Vec<HouseholdPackingList>,
+    pub producers: Vec<ProducerPickingList>,
+}
+
+fn get_household_name(household_hash: ActionHash) -> ExternResult<String> {
+    let record = get_latest_household(household_hash)?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Household not found".to_string())
+    ))?;
+    Ok(Household::try_from(record)?.name)
+}
+
+fn get_producer_name(producer_hash: ActionHash) -> ExternResult<String> {
+    let record = get_latest_producer(producer_hash)?.ok_or(wasm_error!(WasmErrorInner::Guest(
+        "Producer not found".to_string()
+    )))?;
+    Ok(Producer::try_from(record)?.name)
+}
+
+/// The bag each household takes home on distribution day, and what to pick from each producer
+/// to fill them, with the delivered amounts from the producer deliveries when they are recorded
+#[hdk_extern]
+pub fn get_packing_list(order_hash: ActionHash) -> ExternResult<PackingList> {
+    let mut product_deliveries: BTreeMap<ActionHashB64, ProductDelivery> = BTreeMap::new();
+    for record in get_latest_producer_deliveries_for_order(order_hash.clone())? {
+        product_deliveries.extend(ProducerDelivery::try_from(record)?.products);
+    }
+
+    // Households may have ordered different revisions of the same product
+    let mut products: BTreeMap<ActionHashB64, Product> = BTreeMap::new();
+    let mut households: Vec<HouseholdPackingList> = Vec::new();
+    let mut producers_lines: BTreeMap<ActionHashB64, BTreeMap<ActionHashB64, PackingListLine>> =
+        BTreeMap::new();
+
+    for record in get_household_orders_in_order(order_hash.clone())? {
+        let household_order = HouseholdOrder::try_from(record)?;
+
+        let mut lines: Vec<PackingListLine> = Vec::new();
+        for product_order in household_order.products {
+            let original_product_hash: ActionHashB64 =
+                product_order.original_product_hash.clone().into();
+            let ordered_product_hash: ActionHashB64 =
+                product_order.ordered_product_hash.clone().into();
+            let product = match products.get(&ordered_product_hash) {
+                Some(product) => product.clone(),
+                None => {
+                    let product = get_product(product_order.ordered_product_hash)?;
+                    products.insert(ordered_product_hash, product.clone());
+                    product
+                }
+            };
+            let product_delivery = product_deliveries.get(&original_product_hash);
+
+            let picking_line = producers_lines
+                .entry(product.producer_hash.clone().into())
+                .or_default()
+                .entry(original_product_hash)
+                .or_insert(PackingListLine {
+                    original_product_hash: product_order.original_product_hash.clone(),
+                    product_name: product.name.clone(),
+                    packaging: product.packaging.clone(),
+                    ordered_amount: 0,
+                    delivered_amount: product_delivery.map(
+                        |product_delivery| match product_delivery {
+                            ProductDelivery::Delivered {
+                                delivered_amount, ..
+                            } => delivered_quantity(delivered_amount).0,
+                            ProductDelivery::Missing => 0.0,
+                        },
+                    ),
+                });
+            picking_line.ordered_amount += product_order.amount;
+
+            lines.push(PackingListLine {
+                original_product_hash: product_order.original_product_hash,
+                product_name: product.name,
+                packaging: product.packaging,
+                ordered_amount: product_order.amount,
+                delivered_amount: product_delivery.map(|product_delivery| {
+                    delivered_to_household(product_delivery, &household_order.household_hash).0
+                }),
+            });
+        }
+        lines.sort_by(|line_a, line_b| line_a.product_name.cmp(&line_b.product_name));
+
+        households.push(HouseholdPackingList {
+            household_name: get_household_name(household_order.household_hash.clone())?,
+            household_hash: household_order.household_hash,
+            lines,
+        });
+    }
+    households.sort_by(|household_a, household_b| {
+        household_a.household_name.cmp(&household_b.household_name)
+    });
+
+    let mut producers: Vec<ProducerPickingList> = Vec::new();
+    for (producer_hash, lines) in producers_lines {
+        let mut lines: Vec<PackingListLine> = lines.into_values().collect();
+        lines.sort_by(|line_a, line_b| line_a.product_name.cmp(&line_b.product_name));
+        producers.push(ProducerPickingList {
+            producer_name: get_producer_name(producer_hash.clone().into())?,
+            producer_hash: producer_hash.into(),
+            lines,
+        });
+    }
+    producers
+        .sort_by(|producer_a, producer_b| producer_a.producer_name.cmp(&producer_b.producer_name));
+
+    Ok(PackingList {
+        order_hash,
+        households,
+        producers,
+    })
+}
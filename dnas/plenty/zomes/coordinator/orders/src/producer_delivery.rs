@@ -1,8 +1,23 @@
 use hdk::prelude::*;
 use orders_integrity::*;
 
+use crate::producers::get_latest_producer;
+
+/// Validation reads the liason from `latest_producer_hash`,
+/// so always commit producer deliveries against the latest revision of their producer
+fn with_latest_producer_hash(
+    mut producer_delivery: ProducerDelivery,
+) -> ExternResult<ProducerDelivery> {
+    let latest_producer = get_latest_producer(producer_delivery.producer_hash.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest("Producer not found".to_string())),
+    )?;
+    producer_delivery.latest_producer_hash = latest_producer.action_address().clone();
+    Ok(producer_delivery)
+}
+
 #[hdk_extern]
 pub fn create_producer_delivery(producer_delivery: ProducerDelivery) -> ExternResult<Record> {
+    let producer_delivery = with_latest_producer_hash(producer_delivery)?;
     let producer_delivery_hash =
         create_entry(&EntryTypes::ProducerDelivery(producer_delivery.clone()))?;
     create_link(
@@ -83,9 +98,10 @@ pub struct UpdateProducerDeliveryInput {
 
 #[hdk_extern]
 pub fn update_producer_delivery(input: UpdateProducerDeliveryInput) -> ExternResult<Record> {
+    let updated_producer_delivery = with_latest_producer_hash(input.updated_producer_delivery)?;
     let updated_producer_delivery_hash = update_entry(
         input.previous_producer_delivery_hash,
-        &input.updated_producer_delivery,
+        &updated_producer_delivery,
     )?;
     let record = get(
         updated_producer_delivery_hash.clone(),
@@ -207,3 +223,25 @@ pub fn get_deleted_producer_deliveries_for_order(
         .filter(|(_link, deletes)| !deletes.is_empty())
         .collect())
 }
+
+/// Returns the latest revision of every producer delivery linked from the given order
+pub fn get_latest_producer_deliveries_for_order(
+    order_hash: ActionHash,
+) -> ExternResult<Vec<Record>> {
+    let links = get_producer_deliveries_for_order(order_hash)?;
+    let mut producer_deliveries: Vec<Record> = Vec::new();
+    for link in links {
+        let original_producer_delivery_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        if let Some(latest_producer_delivery) =
+            get_latest_producer_delivery(original_producer_delivery_hash)?
+        {
+            producer_deliveries.push(latest_producer_delivery);
+        }
+    }
+    Ok(producer_deliveries)
+}
@@ -1,5 +1,10 @@
 use hdk::prelude::*;
 use orders_integrity::*;
+use pricing::{billed_packages, total_weight};
+use producers_types::Product;
+
+use crate::producer_delivery::get_latest_producer_deliveries_for_order;
+use crate::reconciliation::{get_catalog_product, get_ordered_product_hashes};
 
 #[hdk_extern]
 pub fn create_producer_invoice(producer_invoice: ProducerInvoice) -> ExternResult<Record> {
@@ -204,3 +209,93 @@ pub fn get_deleted_producer_invoices_for_order(
         .filter(|(_link, deletes)| !deletes.is_empty())
         .collect())
 }
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DraftProducerInvoiceLinesInput {
+    pub order_hash: ActionHash,
+    pub producer_hash: ActionHash,
+}
+
+/// Total amount delivered of a product, across all households, and the changed price if any
+pub fn delivered_quantity(delivered_amount: &DeliveredAmount) -> (f32, Option<u32>) {
+    match delivered_amount {
+        DeliveredAmount::FixedAmountProduct {
+            delivered_products,
+            price_cents_per_unit_changed,
+        } => (
+            delivered_products
+                .iter()
+                .map(|delivery| delivery.amount as f32)
+                .sum(),
+            *price_cents_per_unit_changed,
+        ),
+        DeliveredAmount::EstimatedAmountProduct {
+            delivered_products_by_household,
+            price_cents_per_unit_changed,
+        } => (
+            delivered_products_by_household
+                .iter()
+                .map(|delivery| total_weight(&delivery.products))
+                .sum(),
+            *price_cents_per_unit_changed,
+        ),
+    }
+}
+
+/// Estimated products are priced per package, but delivered by weight or volume
+pub fn billed_quantity(product: &Product, delivered: f32) -> f32 {
+    billed_packages(
+        product.packaging.estimate,
+        product.packaging.amount_per_package,
+        delivered,
+    ) as f32
+}
+
+/// Drafts the invoice lines for the given producer from what it delivered in the order,
+/// for the bookkeeper to check against the invoice file before saving them
+///
+/// Missing products are left out, and estimated products are billed like in the household bills:
+/// by delivered weight or volume divided by the amount per package. Products are priced
+/// with the revision that households ordered, like in the reconciliation of the order
+#[hdk_extern]
+pub fn draft_producer_invoice_lines(
+    input: DraftProducerInvoiceLinesInput,
+) -> ExternResult<Vec<InvoiceLine>> {
+    let ordered_product_hashes = get_ordered_product_hashes(input.order_hash.clone())?;
+    let mut lines: Vec<InvoiceLine> = Vec::new();
+    for record in get_latest_producer_deliveries_for_order(input.order_hash)? {
+        let producer_delivery = ProducerDelivery::try_from(record)?;
+        if producer_delivery.producer_hash.ne(&input.producer_hash) {
+            continue;
+        }
+        for (original_product_hash, product_delivery) in producer_delivery.products {
+            let ProductDelivery::Delivered {
+                delivered_amount, ..
+            } = product_delivery
+            else {
+                continue;
+            };
+            let original_product_hash = ActionHash::from(original_product_hash);
+            let product = get_catalog_product(&original_product_hash, &ordered_product_hashes)?;
+
+            let (delivered, price_cents_per_unit_changed) = delivered_quantity(&delivered_amount);
+            let quantity = billed_quantity(&product, delivered);
+            if quantity <= 0.0 {
+                continue;
+            }
+
+            let line = InvoiceLine::new(
+                original_product_hash,
+                quantity,
+                price_cents_per_unit_changed.unwrap_or(product.price_cents),
+                product.vat_percentage,
+            )
+            .ok_or(wasm_error!(WasmErrorInner::Guest(format!(
+                "The delivered quantity or the VAT of {} are out of range",
+                product.name
+            ))))?;
+            lines.push(line);
+        }
+    }
+    Ok(lines)
+}
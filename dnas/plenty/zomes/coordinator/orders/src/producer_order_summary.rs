@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use orders_integrity::*;
+use producers_types::Product;
+
+use crate::household_order::get_household_orders_in_order;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct HouseholdProductOrder {
+    pub household_hash: ActionHash,
+    pub household_order_hash: ActionHash,
+    pub ordered_product_hash: ActionHash,
+    pub amount: u32,
+    pub price_cents: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProductOrderSummary {
+    pub original_product_hash: ActionHash,
+    pub name: String,
+    pub total_amount: u32,
+    pub total_price_cents: u64,
+    pub households: Vec<HouseholdProductOrder>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProducerOrderSummary {
+    pub order_hash: ActionHash,
+    pub producer_hash: ActionHash,
+    pub products: Vec<ProductOrderSummary>,
+    pub total_price_cents: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetProducerOrderSummaryInput {
+    pub order_hash: ActionHash,
+    pub producer_hash: ActionHash,
+}
+
+pub fn get_product(product_hash: ActionHash) -> ExternResult<Product> {
+    let record = get(product_hash, GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Product not found".to_string())
+    ))?;
+    Product::try_from(record)
+}
+
+/// Adds up what every household ordered from the given producer,
+/// pricing each line with the revision of the product that was ordered
+#[hdk_extern]
+pub fn get_producer_order_summary(
+    input: GetProducerOrderSummaryInput,
+) -> ExternResult<ProducerOrderSummary> {
+    let household_orders = get_household_orders_in_order(input.order_hash.clone())?;
+
+    let mut products: BTreeMap<ActionHashB64, ProductOrderSummary> = BTreeMap::new();
+
+    for record in household_orders {
+        let household_order_hash = record.action_address().clone();
+        let household_order = HouseholdOrder::try_from(record)?;
+
+        for product_order in household_order.products {
+            let product = get_product(product_order.ordered_product_hash.clone())?;
+            if product.producer_hash.ne(&input.producer_hash) {
+                continue;
+            }
+            let summary = products
+                .entry(product_order.original_product_hash.clone().into())
+                .or_insert(ProductOrderSummary {
+                    original_product_hash: product_order.original_product_hash.clone(),
+                    name: product.name.clone(),
+                    total_amount: 0,
+                    total_price_cents: 0,
+                    households: vec![],
+                });
+            summary.total_amount = summary
+                .total_amount
+                .checked_add(product_order.amount)
+                .ok_or(wasm_error!(WasmErrorInner::Guest(format!(
+                    "The total amount ordered of {} is too large",
+                    product.name
+                ))))?;
+            summary.total_price_cents += product_order.amount as u64 * product.price_cents as u64;
+            summary.households.push(HouseholdProductOrder {
+                household_hash: household_order.household_hash.clone(),
+                household_order_hash: household_order_hash.clone(),
+                ordered_product_hash: product_order.ordered_product_hash,
+                amount: product_order.amount,
+                price_cents: product.price_cents,
+            });
+        }
+    }
+
+    let products: Vec<ProductOrderSummary> = products.into_values().collect();
+    let total_price_cents = products
+        .iter()
+        .map(|product| product.total_price_cents)
+        .sum();
+
+    Ok(ProducerOrderSummary {
+        order_hash: input.order_hash,
+        producer_hash: input.producer_hash,
+        products,
+        total_price_cents,
+    })
+}
@@ -0,0 +1,41 @@
+use hdk::prelude::*;
+
+use crate::call_local_zome;
+
+pub const PRODUCERS_ZOME_NAME: &str = "producers";
+
+pub fn get_latest_product(original_product_hash: ActionHash) -> ExternResult<Option<Record>> {
+    call_local_zome(
+        PRODUCERS_ZOME_NAME,
+        "get_latest_product",
+        original_product_hash,
+    )
+}
+
+pub fn get_latest_producer(original_producer_hash: ActionHash) -> ExternResult<Option<Record>> {
+    call_local_zome(
+        PRODUCERS_ZOME_NAME,
+        "get_latest_producer",
+        original_producer_hash,
+    )
+}
+
+pub fn get_all_deletes_for_product(
+    original_product_hash: ActionHash,
+) -> ExternResult<Option<Vec<SignedActionHashed>>> {
+    call_local_zome(
+        PRODUCERS_ZOME_NAME,
+        "get_all_deletes_for_product",
+        original_product_hash,
+    )
+}
+
+pub fn get_all_deletes_for_producer(
+    original_producer_hash: ActionHash,
+) -> ExternResult<Option<Vec<SignedActionHashed>>> {
+    call_local_zome(
+        PRODUCERS_ZOME_NAME,
+        "get_all_deletes_for_producer",
+        original_producer_hash,
+    )
+}
@@ -0,0 +1,152 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use orders_integrity::*;
+use producers_types::Product;
+
+use crate::available_products::{get_available_products_for_order, get_latest_available_products};
+use crate::household_order::get_live_household_orders_for_order;
+use crate::producers::get_latest_product;
+use crate::Signal;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProductAvailability {
+    pub original_product_hash: ActionHash,
+    pub maximum_available: Option<u32>,
+    pub ordered_amount: u64,
+    /// None if the product has no maximum_available,
+    /// negative if concurrent household orders oversubscribed it
+    pub remaining: Option<i64>,
+    pub oversubscribed: bool,
+}
+
+fn get_latest_product_entry(original_product_hash: ActionHash) -> ExternResult<Option<Product>> {
+    let Some(record) = get_latest_product(original_product_hash)? else {
+        return Ok(None);
+    };
+    Ok(Some(Product::try_from(record)?))
+}
+
+/// Adds up the amount ordered of each product, indexed by its original product hash
+/// as u64, so that adding up the u32 amounts of all the households can't overflow
+fn ordered_amounts(
+    household_orders: Vec<Record>,
+    excluded_household_hash: Option<&ActionHash>,
+) -> ExternResult<BTreeMap<ActionHashB64, u64>> {
+    let mut amounts: BTreeMap<ActionHashB64, u64> = BTreeMap::new();
+    for record in household_orders {
+        let household_order = HouseholdOrder::try_from(record)?;
+        if Some(&household_order.household_hash).eq(&excluded_household_hash) {
+            continue;
+        }
+        for product_order in household_order.products {
+            *amounts
+                .entry(product_order.original_product_hash.into())
+                .or_insert(0) += product_order.amount as u64;
+        }
+    }
+    Ok(amounts)
+}
+
+/// Returns the original hashes of all the products currently offered in the given order
+pub fn get_offered_products(order_hash: ActionHash) -> ExternResult<Vec<ActionHash>> {
+    let links = get_available_products_for_order(order_hash)?;
+    let mut products: Vec<ActionHash> = Vec::new();
+    for link in links {
+        let original_available_products_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let Some(record) = get_latest_available_products(original_available_products_hash)? else {
+            continue;
+        };
+        let available_products = AvailableProducts::try_from(record)?;
+        if let ProducerAvailability::Available { available_products } =
+            available_products.producer_availability
+        {
+            products.extend(available_products);
+        }
+    }
+    Ok(products)
+}
+
+/// Computes how many units of each product offered or ordered in the given order are left,
+/// taking into account all the live household orders
+///
+/// The check in `create_household_order` and `update_household_order` races with
+/// the other households, so a product may end up oversubscribed: it's flagged here
+#[hdk_extern]
+pub fn get_remaining_availability(
+    order_hash: ActionHash,
+) -> ExternResult<Vec<ProductAvailability>> {
+    let household_orders = get_live_household_orders_for_order(order_hash.clone())?;
+    let mut amounts = ordered_amounts(household_orders, None)?;
+
+    for original_product_hash in get_offered_products(order_hash)? {
+        amounts.entry(original_product_hash.into()).or_insert(0);
+    }
+
+    let mut availability: Vec<ProductAvailability> = Vec::new();
+    for (original_product_hash, ordered_amount) in amounts {
+        let original_product_hash = ActionHash::from(original_product_hash);
+        let maximum_available = get_latest_product_entry(original_product_hash.clone())?
+            .and_then(|product| product.maximum_available);
+        let remaining = maximum_available
+            .map(|maximum_available| maximum_available as i64 - ordered_amount as i64);
+        availability.push(ProductAvailability {
+            original_product_hash,
+            maximum_available,
+            ordered_amount,
+            remaining,
+            oversubscribed: remaining.map(|remaining| remaining < 0).unwrap_or(false),
+        });
+    }
+    Ok(availability)
+}
+
+/// Refuses the household order if, added to the other households' live orders,
+/// it would exceed the maximum_available of any of its products
+pub fn check_household_order_availability(household_order: &HouseholdOrder) -> ExternResult<()> {
+    let household_orders = get_live_household_orders_for_order(household_order.order_hash.clone())?;
+    let amounts = ordered_amounts(household_orders, Some(&household_order.household_hash))?;
+
+    for product_order in &household_order.products {
+        let Some(product) = get_latest_product_entry(product_order.original_product_hash.clone())?
+        else {
+            continue;
+        };
+        let Some(maximum_available) = product.maximum_available else {
+            continue;
+        };
+        let already_ordered = amounts
+            .get(&product_order.original_product_hash.clone().into())
+            .cloned()
+            .unwrap_or(0);
+        let remaining = (maximum_available as u64).saturating_sub(already_ordered);
+        if product_order.amount as u64 > remaining {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "Only {} units of {} are still available",
+                remaining, product.name
+            ))));
+        }
+    }
+    Ok(())
+}
+
+/// Called after committing a household order, to let the UI know if
+/// a concurrent household order from another agent has oversubscribed a product
+pub fn signal_oversubscribed_products(order_hash: ActionHash) -> ExternResult<()> {
+    let products: Vec<ProductAvailability> = get_remaining_availability(order_hash.clone())?
+        .into_iter()
+        .filter(|product| product.oversubscribed)
+        .collect();
+    if !products.is_empty() {
+        emit_signal(Signal::ProductsOversubscribed {
+            order_hash,
+            products,
+        })?;
+    }
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hdk::prelude::*;
+use orders_integrity::*;
+
+use crate::household_order::get_household_orders_in_order;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProductRevisionOrders {
+    /// The revision of the product that households ordered
+    pub ordered_product_hash: ActionHash,
+    pub orders_hashes: Vec<ActionHash>,
+}
+
+/// The orders in which households ordered the given product, grouped by the product revision they ordered
+///
+/// Only the orders that offered the product can include it, and those are linked from the product
+#[hdk_extern]
+pub fn get_orders_for_product(
+    original_product_hash: ActionHash,
+) -> ExternResult<Vec<ProductRevisionOrders>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            original_product_hash.clone(),
+            LinkTypes::ProductToAvailableProducts,
+        )?
+        .build(),
+    )?;
+    let get_input: Vec<GetInput> = links
+        .into_iter()
+        .map(|link| {
+            Ok(GetInput::new(
+                link.target
+                    .into_action_hash()
+                    .ok_or(wasm_error!(WasmErrorInner::Guest(
+                        "No action hash associated with link".to_string()
+                    )))?
+                    .into(),
+                GetOptions::default(),
+            ))
+        })
+        .collect::<ExternResult<Vec<GetInput>>>()?;
+    let records = HDK.with(|hdk| hdk.borrow().get(get_input))?;
+    let mut offering_orders: BTreeSet<ActionHash> = BTreeSet::new();
+    for record in records.into_iter().flatten() {
+        offering_orders.insert(AvailableProducts::try_from(record)?.order_hash);
+    }
+
+    let mut revisions_orders: BTreeMap<ActionHashB64, Vec<ActionHash>> = BTreeMap::new();
+    for order_hash in offering_orders {
+        for record in get_household_orders_in_order(order_hash.clone())? {
+            let household_order = HouseholdOrder::try_from(record)?;
+            for product_order in household_order.products {
+                if product_order
+                    .original_product_hash
+                    .ne(&original_product_hash)
+                {
+                    continue;
+                }
+                let orders_hashes = revisions_orders
+                    .entry(product_order.ordered_product_hash.into())
+                    .or_default();
+                if !orders_hashes.contains(&order_hash) {
+                    orders_hashes.push(order_hash.clone());
+                }
+            }
+        }
+    }
+
+    Ok(revisions_orders
+        .into_iter()
+        .map(
+            |(ordered_product_hash, orders_hashes)| ProductRevisionOrders {
+                ordered_product_hash: ordered_product_hash.into(),
+                orders_hashes,
+            },
+        )
+        .collect())
+}
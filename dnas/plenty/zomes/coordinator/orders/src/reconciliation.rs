@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hdk::prelude::*;
+use orders_integrity::*;
+use producers_types::Product;
+
+use crate::household_order::get_household_orders_in_order;
+use crate::producer_delivery::get_latest_producer_deliveries_for_order;
+use crate::producer_invoice::{
+    billed_quantity, delivered_quantity, get_latest_producer_invoice,
+    get_producer_invoices_for_order,
+};
+use crate::producer_order_summary::get_product;
+use crate::producers::get_latest_product;
+
+/// Quantities are floats for estimated products, so tiny differences are not reported
+const QUANTITY_TOLERANCE: f32 = 0.001;
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProductDifference {
+    pub original_product_hash: ActionHash,
+    pub delivered_quantity: f32,
+    pub invoiced_quantity: f32,
+    /// Price of the product revision that households ordered
+    pub catalog_price_cents: u32,
+    /// Price recorded in the producer delivery, if it changed from the catalog price
+    pub delivered_price_cents: Option<u32>,
+    /// None if the product is not in the invoice
+    pub invoiced_price_cents: Option<u32>,
+    pub expected_total_cents: u64,
+    pub invoiced_total_cents: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ReconciliationStatus {
+    Matching,
+    Mismatch,
+    /// The producer delivered, but there is no invoice for it yet
+    MissingInvoice,
+    /// There is an invoice for the producer, but no delivery was recorded
+    MissingDelivery,
+    /// The invoice was only uploaded as a file, without line items to compare
+    InvoiceWithoutLines,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ProducerReconciliation {
+    pub producer_hash: ActionHash,
+    pub producer_delivery_hash: Option<ActionHash>,
+    pub producer_invoices_hashes: Vec<ActionHash>,
+    pub status: ReconciliationStatus,
+    pub expected_total_cents: u64,
+    pub invoiced_total_cents: u64,
+    pub differences: Vec<ProductDifference>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrderReconciliation {
+    pub order_hash: ActionHash,
+    pub producers: Vec<ProducerReconciliation>,
+}
+
+/// Product revisions that households ordered, by original product hash
+pub(crate) fn get_ordered_product_hashes(
+    order_hash: ActionHash,
+) -> ExternResult<BTreeMap<ActionHashB64, ActionHash>> {
+    let mut ordered_product_hashes: BTreeMap<ActionHashB64, ActionHash> = BTreeMap::new();
+    for record in get_household_orders_in_order(order_hash)? {
+        let household_order = HouseholdOrder::try_from(record)?;
+        for product_order in household_order.products {
+            ordered_product_hashes
+                .entry(product_order.original_product_hash.into())
+                .or_insert(product_order.ordered_product_hash);
+        }
+    }
+    Ok(ordered_product_hashes)
+}
+
+/// The product revision that households ordered, or its latest revision if nobody ordered it
+pub(crate) fn get_catalog_product(
+    original_product_hash: &ActionHash,
+    ordered_product_hashes: &BTreeMap<ActionHashB64, ActionHash>,
+) -> ExternResult<Product> {
+    if let Some(ordered_product_hash) =
+        ordered_product_hashes.get(&original_product_hash.clone().into())
+    {
+        return get_product(ordered_product_hash.clone());
+    }
+    let record = get_latest_product(original_product_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Product not found".to_string())
+    ))?;
+    Product::try_from(record)
+}
+
+struct ExpectedLine {
+    line: InvoiceLine,
+    catalog_price_cents: u32,
+    delivered_price_cents: Option<u32>,
+}
+
+/// Expected lines for everything that was delivered, priced with the delivered price if it
+/// changed, or the price of the product revision that was ordered
+fn expected_lines(
+    producer_delivery: &ProducerDelivery,
+    ordered_product_hashes: &BTreeMap<ActionHashB64, ActionHash>,
+) -> ExternResult<BTreeMap<ActionHashB64, ExpectedLine>> {
+    let mut lines = BTreeMap::new();
+    for (original_product_hash, product_delivery) in &producer_delivery.products {
+        let ProductDelivery::Delivered {
+            delivered_amount, ..
+        } = product_delivery
+        else {
+            continue;
+        };
+        let product = get_catalog_product(
+            &original_product_hash.clone().into(),
+            ordered_product_hashes,
+        )?;
+        let (delivered, price_cents_per_unit_changed) = delivered_quantity(delivered_amount);
+        let quantity = billed_quantity(&product, delivered);
+        if quantity <= 0.0 {
+            continue;
+        }
+        let line = InvoiceLine::new(
+            original_product_hash.clone().into(),
+            quantity,
+            price_cents_per_unit_changed.unwrap_or(product.price_cents),
+            product.vat_percentage,
+        )
+        .ok_or(wasm_error!(WasmErrorInner::Guest(format!(
+            "The delivered quantity or the VAT of {} are out of range",
+            product.name
+        ))))?;
+        lines.insert(
+            original_product_hash.clone(),
+            ExpectedLine {
+                line,
+                catalog_price_cents: product.price_cents,
+                delivered_price_cents: price_cents_per_unit_changed,
+            },
+        );
+    }
+    Ok(lines)
+}
+
+fn reconcile_producer(
+    producer_hash: ActionHash,
+    producer_delivery: Option<Record>,
+    producer_invoices: Vec<Record>,
+    ordered_product_hashes: &BTreeMap<ActionHashB64, ActionHash>,
+) -> ExternResult<ProducerReconciliation> {
+    let producer_delivery_hash = producer_delivery
+        .as_ref()
+        .map(|record| record.action_address().clone());
+    let producer_invoices_hashes: Vec<ActionHash> = producer_invoices
+        .iter()
+        .map(|record| record.action_address().clone())
+        .collect();
+
+    let expected = match producer_delivery {
+        Some(record) => {
+            expected_lines(&ProducerDelivery::try_from(record)?, ordered_product_hashes)?
+        }
+        None => BTreeMap::new(),
+    };
+    let mut invoiced: BTreeMap<ActionHashB64, Vec<InvoiceLine>> = BTreeMap::new();
+    let mut has_lines = false;
+    for record in producer_invoices {
+        for line in ProducerInvoice::try_from(record)?.lines {
+            has_lines = true;
+            invoiced
+                .entry(line.original_product_hash.clone().into())
+                .or_default()
+                .push(line);
+        }
+    }
+
+    let products: BTreeSet<ActionHashB64> =
+        expected.keys().chain(invoiced.keys()).cloned().collect();
+    let mut differences: Vec<ProductDifference> = Vec::new();
+    for original_product_hash in products {
+        let expected_line = expected.get(&original_product_hash);
+        let invoiced_lines = invoiced
+            .get(&original_product_hash)
+            .cloned()
+            .unwrap_or_default();
+
+        let invoiced_quantity: f32 = invoiced_lines.iter().map(|line| line.quantity).sum();
+        let invoiced_total_cents: u64 = invoiced_lines.iter().map(|line| line.total_cents).sum();
+        let invoiced_price_cents = invoiced_lines.first().map(|line| line.unit_price_cents);
+
+        let (delivered_quantity, expected_total_cents, expected_price_cents) = match expected_line {
+            Some(expected) => (
+                expected.line.quantity,
+                expected.line.total_cents,
+                Some(expected.line.unit_price_cents),
+            ),
+            None => (0.0, 0, None),
+        };
+        let (catalog_price_cents, delivered_price_cents) = match expected_line {
+            Some(expected) => (expected.catalog_price_cents, expected.delivered_price_cents),
+            None => (
+                get_catalog_product(
+                    &original_product_hash.clone().into(),
+                    ordered_product_hashes,
+                )?
+                .price_cents,
+                None,
+            ),
+        };
+
+        let matches = (delivered_quantity - invoiced_quantity).abs() < QUANTITY_TOLERANCE
+            && expected_total_cents == invoiced_total_cents
+            && invoiced_lines
+                .iter()
+                .all(|line| Some(line.unit_price_cents) == expected_price_cents);
+        if !matches {
+            differences.push(ProductDifference {
+                original_product_hash: original_product_hash.into(),
+                delivered_quantity,
+                invoiced_quantity,
+                catalog_price_cents,
+                delivered_price_cents,
+                invoiced_price_cents,
+                expected_total_cents,
+                invoiced_total_cents,
+            });
+        }
+    }
+
+    let status = if producer_delivery_hash.is_none() {
+        ReconciliationStatus::MissingDelivery
+    } else if producer_invoices_hashes.is_empty() {
+        ReconciliationStatus::MissingInvoice
+    } else if !has_lines {
+        ReconciliationStatus::InvoiceWithoutLines
+    } else if differences.is_empty() {
+        ReconciliationStatus::Matching
+    } else {
+        ReconciliationStatus::Mismatch
+    };
+
+    Ok(ProducerReconciliation {
+        producer_hash,
+        producer_delivery_hash,
+        producer_invoices_hashes,
+        status,
+        expected_total_cents: expected
+            .values()
+            .map(|expected| expected.line.total_cents)
+            .sum(),
+        invoiced_total_cents: invoiced
+            .values()
+            .flatten()
+            .map(|line| line.total_cents)
+            .sum(),
+        differences,
+    })
+}
+
+/// Compares what each producer delivered in the given order with what they invoiced
+///
+/// Producers that delivered but haven't been invoiced yet, and invoices without a delivery,
+/// are reported with their own status
+#[hdk_extern]
+pub fn reconcile_order(order_hash: ActionHash) -> ExternResult<OrderReconciliation> {
+    let ordered_product_hashes = get_ordered_product_hashes(order_hash.clone())?;
+
+    let mut producer_deliveries: BTreeMap<ActionHashB64, Record> = BTreeMap::new();
+    for record in get_latest_producer_deliveries_for_order(order_hash.clone())? {
+        let producer_delivery = ProducerDelivery::try_from(record.clone())?;
+        producer_deliveries.insert(producer_delivery.producer_hash.into(), record);
+    }
+
+    let mut producer_invoices: BTreeMap<ActionHashB64, Vec<Record>> = BTreeMap::new();
+    for link in get_producer_invoices_for_order(order_hash.clone())? {
+        let original_producer_invoice_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let Some(record) = get_latest_producer_invoice(original_producer_invoice_hash)? else {
+            continue;
+        };
+        let producer_invoice = ProducerInvoice::try_from(record.clone())?;
+        producer_invoices
+            .entry(producer_invoice.producer_hash.into())
+            .or_default()
+            .push(record);
+    }
+
+    let producers_hashes: BTreeSet<ActionHashB64> = producer_deliveries
+        .keys()
+        .chain(producer_invoices.keys())
+        .cloned()
+        .collect();
+    let producers = producers_hashes
+        .into_iter()
+        .map(|producer_hash| {
+            reconcile_producer(
+                producer_hash.clone().into(),
+                producer_deliveries.remove(&producer_hash),
+                producer_invoices.remove(&producer_hash).unwrap_or_default(),
+                &ordered_product_hashes,
+            )
+        })
+        .collect::<ExternResult<Vec<ProducerReconciliation>>>()?;
+
+    Ok(OrderReconciliation {
+        order_hash,
+        producers,
+    })
+}
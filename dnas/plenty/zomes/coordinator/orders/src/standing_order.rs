@@ -0,0 +1,235 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use orders_integrity::*;
+
+use crate::household_order::{create_household_order, get_live_household_orders_for_order};
+use crate::households::get_my_household_hash;
+use crate::order::get_latest_order;
+use crate::producers::get_latest_product;
+use crate::product_availability::{get_offered_products, get_remaining_availability};
+
+#[hdk_extern]
+pub fn create_standing_order(standing_order: StandingOrder) -> ExternResult<Record> {
+    let standing_order_hash = create_entry(&EntryTypes::StandingOrder(standing_order.clone()))?;
+    create_link(
+        standing_order.household_hash.clone(),
+        standing_order_hash.clone(),
+        LinkTypes::HouseholdToStandingOrders,
+        (),
+    )?;
+    let record = get(standing_order_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the newly created StandingOrder".to_string())
+    ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn get_latest_standing_order(
+    original_standing_order_hash: ActionHash,
+) -> ExternResult<Option<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(
+            original_standing_order_hash.clone(),
+            LinkTypes::StandingOrderUpdates,
+        )?
+        .build(),
+    )?;
+    let latest_link = links
+        .into_iter()
+        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
+    let latest_standing_order_hash = match latest_link {
+        Some(link) => {
+            link.target
+                .clone()
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?
+        }
+        None => original_standing_order_hash.clone(),
+    };
+    get(latest_standing_order_hash, GetOptions::default())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateStandingOrderInput {
+    pub original_standing_order_hash: ActionHash,
+    pub previous_standing_order_hash: ActionHash,
+    pub updated_standing_order: StandingOrder,
+}
+
+#[hdk_extern]
+pub fn update_standing_order(input: UpdateStandingOrderInput) -> ExternResult<Record> {
+    let updated_standing_order_hash = update_entry(
+        input.previous_standing_order_hash.clone(),
+        &input.updated_standing_order,
+    )?;
+    create_link(
+        input.original_standing_order_hash.clone(),
+        updated_standing_order_hash.clone(),
+        LinkTypes::StandingOrderUpdates,
+        (),
+    )?;
+    let record =
+        get(updated_standing_order_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Could not find the newly updated StandingOrder".to_string())
+        ))?;
+    Ok(record)
+}
+
+#[hdk_extern]
+pub fn delete_standing_order(original_standing_order_hash: ActionHash) -> ExternResult<ActionHash> {
+    let record = get(original_standing_order_hash.clone(), GetOptions::default())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest("StandingOrder not found".to_string())),
+    )?;
+    let standing_order = StandingOrder::try_from(record)?;
+    let links = get_standing_orders_for_household(standing_order.household_hash)?;
+    for link in links {
+        if let Some(action_hash) = link.target.into_action_hash() {
+            if action_hash == original_standing_order_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    delete_entry(original_standing_order_hash)
+}
+
+#[hdk_extern]
+pub fn get_standing_orders_for_household(household_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(household_hash, LinkTypes::HouseholdToStandingOrders)?
+            .build(),
+    )
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum SkippedReason {
+    NotOffered,
+    NotEnoughAvailable { remaining: u32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SkippedStandingOrderLine {
+    pub original_product_hash: ActionHash,
+    pub amount: u32,
+    pub reason: SkippedReason,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AppliedStandingOrder {
+    /// None if none of the lines of the standing order could be ordered
+    pub household_order: Option<Record>,
+    pub skipped_lines: Vec<SkippedStandingOrderLine>,
+}
+
+/// Creates the household order of the calling agent's household for the given open order,
+/// from the latest revision of its standing order
+///
+/// Only the products offered in the order are kept, and the rest of the lines are returned
+#[hdk_extern]
+pub fn apply_standing_order(order_hash: ActionHash) -> ExternResult<AppliedStandingOrder> {
+    let latest_order = get_latest_order(order_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Order not found".to_string())
+    ))?;
+    let OrderStatus::Open { .. } = Order::try_from(latest_order)?.status else {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Standing orders can only be applied to Open orders".to_string()
+        )));
+    };
+
+    let household_hash = get_my_household_hash()?;
+    for record in get_live_household_orders_for_order(order_hash.clone())? {
+        if HouseholdOrder::try_from(record)?
+            .household_hash
+            .eq(&household_hash)
+        {
+            return Err(wasm_error!(WasmErrorInner::Guest(
+                "Your household has already ordered in this order".to_string()
+            )));
+        }
+    }
+
+    let latest_link = get_standing_orders_for_household(household_hash.clone())?
+        .into_iter()
+        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp))
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Your household doesn't have a standing order".to_string()
+        )))?;
+    let original_standing_order_hash =
+        latest_link
+            .target
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let standing_order_record = get_latest_standing_order(original_standing_order_hash)?.ok_or(
+        wasm_error!(WasmErrorInner::Guest("StandingOrder not found".to_string())),
+    )?;
+    let standing_order = StandingOrder::try_from(standing_order_record)?;
+
+    let offered_products = get_offered_products(order_hash.clone())?;
+    let remaining: BTreeMap<ActionHashB64, i64> = get_remaining_availability(order_hash.clone())?
+        .into_iter()
+        .filter_map(|availability| {
+            availability
+                .remaining
+                .map(|remaining| (availability.original_product_hash.into(), remaining))
+        })
+        .collect();
+
+    let mut products: Vec<ProductOrder> = Vec::new();
+    let mut skipped_lines: Vec<SkippedStandingOrderLine> = Vec::new();
+    for line in standing_order.products {
+        let latest_product = if offered_products.contains(&line.original_product_hash) {
+            get_latest_product(line.original_product_hash.clone())?
+        } else {
+            None
+        };
+        let Some(latest_product) = latest_product else {
+            skipped_lines.push(SkippedStandingOrderLine {
+                original_product_hash: line.original_product_hash,
+                amount: line.amount,
+                reason: SkippedReason::NotOffered,
+            });
+            continue;
+        };
+        if let Some(remaining) = remaining.get(&line.original_product_hash.clone().into()) {
+            if (line.amount as i64) > *remaining {
+                skipped_lines.push(SkippedStandingOrderLine {
+                    original_product_hash: line.original_product_hash,
+                    amount: line.amount,
+                    reason: SkippedReason::NotEnoughAvailable {
+                        remaining: (*remaining).max(0) as u32,
+                    },
+                });
+                continue;
+            }
+        }
+        products.push(ProductOrder {
+            original_product_hash: line.original_product_hash,
+            ordered_product_hash: latest_product.action_address().clone(),
+            amount: line.amount,
+        });
+    }
+
+    if products.is_empty() {
+        return Ok(AppliedStandingOrder {
+            household_order: None,
+            skipped_lines,
+        });
+    }
+
+    let household_order = create_household_order(HouseholdOrder {
+        order_hash: order_hash.clone(),
+        latest_order_hash: order_hash,
+        household_hash,
+        products,
+    })?;
+
+    Ok(AppliedStandingOrder {
+        household_order: Some(household_order),
+        skipped_lines,
+    })
+}
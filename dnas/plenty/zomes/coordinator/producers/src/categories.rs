@@ -1,9 +1,52 @@
+use std::collections::BTreeSet;
+
 use hdk::prelude::*;
-use producers_integrity::LinkTypes;
+use producers_integrity::*;
+
+use crate::product::{get_latest_product, update_product, UpdateProductInput};
+
+/// Deepest that categories can be nested, which bounds the walks over the categories tree
+pub const MAX_CATEGORY_DEPTH: usize = 4;
+
+/// Categories are nested with "/", so "Vegetables/Roots" is the "Roots" subcategory of "Vegetables"
+pub fn category_components(category: &str) -> Vec<String> {
+    category
+        .split('/')
+        .map(|component| component.trim())
+        .filter(|component| !component.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+pub fn normalize_category(category: &str) -> String {
+    category_components(category).join("/")
+}
+
+pub fn normalize_categories(categories: &[String]) -> BTreeSet<String> {
+    categories
+        .iter()
+        .map(|category| normalize_category(category))
+        .filter(|category| !category.is_empty())
+        .collect()
+}
 
 pub fn category_path(category: String) -> ExternResult<TypedPath> {
+    let components = category_components(&category);
+    if components.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Category can't be empty".to_string()
+        )));
+    }
+    if components.len() > MAX_CATEGORY_DEPTH {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Categories can't be nested more than {MAX_CATEGORY_DEPTH} levels deep"
+        ))));
+    }
+
     let mut path = all_categories_path()?;
-    path.path.append_component(Component::from(category));
+    for component in components {
+        path.path.append_component(Component::from(component));
+    }
     Ok(path)
 }
 
@@ -11,27 +54,312 @@ pub fn all_categories_path() -> ExternResult<TypedPath> {
     Path::from("all_categories").typed(LinkTypes::CategoriesPath)
 }
 
-#[hdk_extern]
-pub fn get_all_categories() -> ExternResult<Vec<String>> {
-    let path = all_categories_path()?;
+fn category_for_path(path: &TypedPath) -> ExternResult<String> {
+    let components: Vec<String> = path
+        .path
+        .as_ref()
+        .iter()
+        .skip(1)
+        .map(|component| {
+            String::try_from(component).map_err(|_| {
+                wasm_error!(WasmErrorInner::Guest(
+                    "Malformed category path component".to_string()
+                ))
+            })
+        })
+        .collect::<ExternResult<Vec<String>>>()?;
+    Ok(components.join("/"))
+}
+
+fn get_category_to_product_links(path: &TypedPath) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::CategoryToProduct)?
+            .build(),
+    )
+}
+
+/// The CategoryToProduct links of each of the given paths, in a single call
+fn get_category_to_product_links_batch(paths: &[TypedPath]) -> ExternResult<Vec<Vec<Link>>> {
+    let inputs = paths
+        .iter()
+        .map(|path| {
+            Ok(GetLinksInputBuilder::try_new(
+                path.path_entry_hash()?,
+                LinkTypes::CategoryToProduct,
+            )?
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    HDK.with(|hdk| hdk.borrow().get_links(inputs))
+}
+
+fn category_depth(path: &TypedPath) -> usize {
+    path.path.as_ref().len().saturating_sub(1)
+}
+
+/// The subcategories of the given path, or none if it's already as deep as categories can be
+fn children_paths(path: &TypedPath) -> ExternResult<Vec<TypedPath>> {
+    if category_depth(path) >= MAX_CATEGORY_DEPTH {
+        return Ok(vec![]);
+    }
+    path.children_paths()
+}
+
+/// The subcategories of all the given paths
+fn next_level(level: &[TypedPath]) -> ExternResult<Vec<TypedPath>> {
+    let mut next_level = Vec::new();
+    for path in level {
+        next_level.extend(children_paths(path)?);
+    }
+    Ok(next_level)
+}
+
+/// The CategoryToProduct links of the category and of all its subcategories,
+/// fetched one level of the tree at a time
+fn get_category_tree_links(path: &TypedPath) -> ExternResult<Vec<Link>> {
+    let mut links = Vec::new();
+    let mut level = vec![path.clone()];
+    while !level.is_empty() {
+        links.extend(
+            get_category_to_product_links_batch(&level)?
+                .into_iter()
+                .flatten(),
+        );
+        level = next_level(&level)?;
+    }
+    Ok(links)
+}
 
-    let children = path.children_paths()?;
+/// Whether there is any product in the category or in any of its subcategories
+fn has_products(path: &TypedPath) -> ExternResult<bool> {
+    let mut level = vec![path.clone()];
+    while !level.is_empty() {
+        let links = get_category_to_product_links_batch(&level)?;
+        if links.iter().any(|links| !links.is_empty()) {
+            return Ok(true);
+        }
+        level = next_level(&level)?;
+    }
+    Ok(false)
+}
 
-    let categories: Vec<String> = children
+/// The subcategories of the given path, sorted by name
+fn sorted_children(path: &TypedPath) -> ExternResult<Vec<(String, TypedPath)>> {
+    let mut children = children_paths(path)?
         .into_iter()
-        .filter_map(|child| child.path.leaf().cloned())
-        .filter_map(|leaf| String::try_from(&leaf).ok())
-        .collect();
+        .map(|child| Ok((category_for_path(&child)?, child)))
+        .collect::<ExternResult<Vec<(String, TypedPath)>>>()?;
+    children.sort_by(|(category_a, _), (category_b, _)| category_a.cmp(category_b));
+    Ok(children)
+}
+
+/// All the categories under the given path that have products, each one followed by its subcategories
+fn non_empty_categories(path: &TypedPath) -> ExternResult<Vec<String>> {
+    let children = sorted_children(path)?;
+    let children_paths: Vec<TypedPath> = children.iter().map(|(_, child)| child.clone()).collect();
+    let children_links = get_category_to_product_links_batch(&children_paths)?;
+
+    let mut categories: Vec<String> = Vec::new();
+    for ((category, child), links) in children.into_iter().zip(children_links) {
+        let subcategories = non_empty_categories(&child)?;
+        if !subcategories.is_empty() || !links.is_empty() {
+            categories.push(category);
+            categories.extend(subcategories);
+        }
+    }
     Ok(categories)
 }
 
+#[hdk_extern]
+pub fn get_all_categories() -> ExternResult<Vec<String>> {
+    non_empty_categories(&all_categories_path()?)
+}
+
+/// The direct subcategories of the given category that have products,
+/// or the top level categories if the given category is empty
+#[hdk_extern]
+pub fn get_subcategories(category: String) -> ExternResult<Vec<String>> {
+    let path = if normalize_category(&category).is_empty() {
+        all_categories_path()?
+    } else {
+        category_path(category)?
+    };
+
+    let mut subcategories: Vec<String> = Vec::new();
+    for (category, child) in sorted_children(&path)? {
+        if has_products(&child)? {
+            subcategories.push(category);
+        }
+    }
+    Ok(subcategories)
+}
+
 #[hdk_extern]
 pub fn get_products_for_category(category: String) -> ExternResult<Vec<Link>> {
     let path = category_path(category)?;
 
-    let links = get_links(
-        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::CategoryToProduct)?
+    get_category_to_product_links(&path)
+}
+
+pub fn link_product_to_categories(
+    original_product_hash: &ActionHash,
+    categories: &BTreeSet<String>,
+) -> ExternResult<()> {
+    for category in categories {
+        let path = category_path(category.clone())?;
+        path.ensure()?;
+        create_link(
+            path.path_entry_hash()?,
+            original_product_hash.clone(),
+            LinkTypes::CategoryToProduct,
+            (),
+        )?;
+    }
+    Ok(())
+}
+
+pub fn unlink_product_from_categories(
+    original_product_hash: &ActionHash,
+    categories: &BTreeSet<String>,
+) -> ExternResult<()> {
+    for category in categories {
+        let path = category_path(category.clone())?;
+        for link in get_category_to_product_links(&path)? {
+            if let Some(target) = link.target.into_action_hash() {
+                if target.eq(original_product_hash) {
+                    delete_link(link.create_link_hash)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The products in the given category or in any of its subcategories
+fn get_products_in_category_tree(path: &TypedPath) -> ExternResult<BTreeSet<ActionHash>> {
+    Ok(get_category_tree_links(path)?
+        .into_iter()
+        .filter_map(|link| link.target.into_action_hash())
+        .collect())
+}
+
+/// Renaming and merging categories updates products of every producer, so it's only for
+/// order managers. The integrity zome only checks it when the categories are removed from
+/// the tree, after all the products have been updated
+fn check_can_change_categories() -> ExternResult<()> {
+    let agent_info = agent_info()?;
+    let ValidateCallbackResult::Valid =
+        validate_can_change_categories(&agent_info.agent_initial_pubkey, &agent_info.chain_head.0)?
+    else {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Only the order managers can rename or merge categories".to_string()
+        )));
+    };
+    Ok(())
+}
+
+/// Replaces the `from` category, and the categories nested in it, with the `into` category
+fn move_category(category: &str, from: &str, into: &str) -> String {
+    if category.eq(from) {
+        return into.to_string();
+    }
+    match category.strip_prefix(&format!("{from}/")) {
+        Some(subcategory) => format!("{into}/{subcategory}"),
+        None => category.to_string(),
+    }
+}
+
+fn move_category_products(from: String, into: String) -> ExternResult<()> {
+    let from = normalize_category(&from);
+    let into = normalize_category(&into);
+    if from.is_empty() || into.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Category can't be empty".to_string()
+        )));
+    }
+    if into.eq(&from) || into.starts_with(&format!("{from}/")) {
+        return Err(wasm_error!(WasmErrorInner::Guest(
+            "Can't move a category into itself".to_string()
+        )));
+    }
+
+    let path = category_path(from.clone())?;
+    let parent_path = path
+        .parent()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Category has no parent".to_string()
+        )))?
+        .typed(LinkTypes::CategoriesPath)?;
+    let path_hash: AnyLinkableHash = path.path_entry_hash()?.into();
+    let category_links: Vec<Link> = get_links(
+        GetLinksInputBuilder::try_new(parent_path.path_entry_hash()?, LinkTypes::CategoriesPath)?
             .build(),
-    )?;
-    Ok(links)
+    )?
+    .into_iter()
+    .filter(|link| link.target.eq(&path_hash))
+    .collect();
+    if category_links.is_empty() {
+        return Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Category {from} not found"
+        ))));
+    }
+
+    for original_product_hash in get_products_in_category_tree(&path)? {
+        let Some(latest_record) = get_latest_product(original_product_hash.clone())? else {
+            continue;
+        };
+        let previous_product_hash = latest_record.action_address().clone();
+        let mut product = Product::try_from(latest_record)?;
+
+        let categories: BTreeSet<String> = normalize_categories(&product.categories)
+            .into_iter()
+            .map(|category| move_category(&category, &from, &into))
+            .collect();
+        product.categories = categories.into_iter().collect();
+
+        update_product(UpdateProductInput {
+            original_product_hash,
+            previous_product_hash,
+            updated_product: product,
+        })?;
+    }
+
+    for link in category_links {
+        delete_link(link.create_link_hash)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RenameCategoryInput {
+    pub from: String,
+    pub to: String,
+}
+
+/// Renames the category and its subcategories, moving all their products. Only for order managers
+#[hdk_extern]
+pub fn rename_category(input: RenameCategoryInput) -> ExternResult<()> {
+    check_can_change_categories()?;
+    move_category_products(input.from, input.to)
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MergeCategoriesInput {
+    pub categories: Vec<String>,
+    pub into: String,
+}
+
+/// Moves all the products of the given categories, and of their subcategories,
+/// into the `into` category. Only for order managers
+#[hdk_extern]
+pub fn merge_categories(input: MergeCategoriesInput) -> ExternResult<()> {
+    check_can_change_categories()?;
+    for category in input.categories {
+        if normalize_category(&category).eq(&normalize_category(&input.into)) {
+            continue;
+        }
+        move_category_products(category, input.into.clone())?;
+    }
+    Ok(())
 }
@@ -3,8 +3,11 @@ use producers_integrity::*;
 
 pub mod all_producers;
 pub mod categories;
+pub mod price_history;
 pub mod producer;
 pub mod product;
+pub mod product_import;
+pub mod product_search;
 
 #[hdk_extern]
 pub fn init() -> ExternResult<InitCallbackResult> {
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use producers_integrity::*;
+
+use crate::product::get_all_revisions_for_product;
+
+/// A revision of the product that changed its price, VAT or margin
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PriceChange {
+    pub product_hash: ActionHash,
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub price_cents: u32,
+    pub vat_percentage: f32,
+    pub margin_percentage: Option<f32>,
+    /// Orders in which households ordered the product while this price was active
+    pub orders_hashes: Vec<ActionHash>,
+}
+
+/// Mirrors the `ProductRevisionOrders` returned by `get_orders_for_product` in the orders zome
+#[derive(Serialize, Deserialize, Debug)]
+struct ProductRevisionOrders {
+    ordered_product_hash: ActionHash,
+    orders_hashes: Vec<ActionHash>,
+}
+
+fn get_orders_for_product(
+    original_product_hash: ActionHash,
+) -> ExternResult<Vec<ProductRevisionOrders>> {
+    let response = call(
+        CallTargetCell::Local,
+        ZomeName::from("orders"),
+        FunctionName::from("get_orders_for_product"),
+        None,
+        original_product_hash,
+    )?;
+    match response {
+        ZomeCallResponse::Ok(result) => result.decode().map_err(|e| wasm_error!(e)),
+        _ => Err(wasm_error!(WasmErrorInner::Guest(format!(
+            "Error getting the orders for the product: {response:?}"
+        )))),
+    }
+}
+
+fn price_changed(previous: &PriceChange, product: &Product) -> bool {
+    previous.price_cents != product.price_cents
+        || previous.vat_percentage != product.vat_percentage
+        || previous.margin_percentage != product.margin_percentage
+}
+
+/// Every change in the price, VAT or margin of the given product, oldest first,
+/// with who made it and the orders in which each price was active
+#[hdk_extern]
+pub fn get_price_history(original_product_hash: ActionHash) -> ExternResult<Vec<PriceChange>> {
+    let mut revisions = get_all_revisions_for_product(original_product_hash.clone())?;
+    revisions.sort_by_key(|record| record.action().timestamp());
+    if revisions.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut price_changes: Vec<PriceChange> = Vec::new();
+    // Index of the price change that was active for each revision
+    let mut revisions_prices: BTreeMap<ActionHashB64, usize> = BTreeMap::new();
+    for record in revisions {
+        let product_hash = record.action_address().clone();
+        let author = record.action().author().clone();
+        let timestamp = record.action().timestamp();
+        let product = Product::try_from(record)?;
+
+        let changed = match price_changes.last() {
+            Some(previous) => price_changed(previous, &product),
+            None => true,
+        };
+        if changed {
+            price_changes.push(PriceChange {
+                product_hash: product_hash.clone(),
+                author,
+                timestamp,
+                price_cents: product.price_cents,
+                vat_percentage: product.vat_percentage,
+                margin_percentage: product.margin_percentage,
+                orders_hashes: vec![],
+            });
+        }
+        revisions_prices.insert(product_hash.into(), price_changes.len() - 1);
+    }
+
+    for revision_orders in get_orders_for_product(original_product_hash)? {
+        let Some(index) = revisions_prices.get(&revision_orders.ordered_product_hash.into()) else {
+            continue;
+        };
+        let orders_hashes = &mut price_changes[*index].orders_hashes;
+        for order_hash in revision_orders.orders_hashes {
+            if !orders_hashes.contains(&order_hash) {
+                orders_hashes.push(order_hash);
+            }
+        }
+    }
+
+    Ok(price_changes)
+}
@@ -25,35 +25,30 @@ pub fn create_producer(producer: Producer) -> ExternResult<Record> {
     )?;
     Ok(record)
 }
+/// Follows the earliest update of each revision, so that an update made
+/// from an outdated revision never becomes the latest one
 #[hdk_extern]
 pub fn get_latest_producer(
     original_producer_hash: ActionHash,
 ) -> ExternResult<Option<Record>> {
-    let links = get_links(
-        GetLinksInputBuilder::try_new(
-                original_producer_hash.clone(),
-                LinkTypes::ProducerUpdates,
-            )?
-            .build(),
-    )?;
-    let latest_link = links
-        .into_iter()
-        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
-    let latest_producer_hash = match latest_link {
-        Some(link) => {
-            link.target
-                .clone()
-                .into_action_hash()
-                .ok_or(
-                    wasm_error!(
-                        WasmErrorInner::Guest("No action hash associated with link"
-                        .to_string())
-                    ),
-                )?
-        }
-        None => original_producer_hash.clone(),
-    };
-    get(latest_producer_hash, GetOptions::default())
+    let mut revisions = get_all_revisions_for_producer(original_producer_hash)?;
+    if revisions.is_empty() {
+        return Ok(None);
+    }
+    let mut latest = revisions.remove(0);
+    revisions.sort_by_key(|revision| revision.action().timestamp());
+    while let Some(next) = revisions
+        .iter()
+        .find(|revision| match revision.action() {
+            Action::Update(update) => {
+                update.original_action_address.eq(latest.action_address())
+            }
+            _ => false,
+        })
+    {
+        latest = next.clone();
+    }
+    Ok(Some(latest))
 }
 #[hdk_extern]
 pub fn get_original_producer(
@@ -119,6 +114,18 @@ pub struct UpdateProducerInput {
 }
 #[hdk_extern]
 pub fn update_producer(input: UpdateProducerInput) -> ExternResult<Record> {
+    let latest_producer = get_latest_producer(input.original_producer_hash.clone())?
+        .ok_or(
+            wasm_error!(WasmErrorInner::Guest("Producer not found".to_string())),
+        )?;
+    if latest_producer.action_address().ne(&input.previous_producer_hash) {
+        return Err(
+            wasm_error!(
+                WasmErrorInner::Guest("Only the latest revision of a Producer can be updated"
+                .to_string())
+            ),
+        );
+    }
     let updated_producer_hash = update_entry(
         input.previous_producer_hash.clone(),
         &input.updated_producer,
@@ -1,7 +1,22 @@
+use std::collections::BTreeSet;
+
 use hdk::prelude::*;
 use producers_integrity::*;
 
-use crate::categories::category_path;
+use crate::categories::{
+    link_product_to_categories, normalize_categories, unlink_product_from_categories,
+};
+use crate::producer::get_latest_producer;
+use crate::product_search::{index_product_name, unindex_product_name};
+
+/// The editors of the latest revision of the producer are the ones who can edit its products
+fn with_latest_producer_hash(mut product: Product) -> ExternResult<Product> {
+    let latest_producer = get_latest_producer(product.producer_hash.clone())?.ok_or(
+        wasm_error!(WasmErrorInner::Guest("Producer not found".to_string())),
+    )?;
+    product.latest_producer_hash = latest_producer.action_address().clone();
+    Ok(product)
+}
 
 #[hdk_extern]
 pub fn create_products(products: Vec<Product>) -> ExternResult<()> {
@@ -14,6 +29,7 @@ pub fn create_products(products: Vec<Product>) -> ExternResult<()> {
 
 #[hdk_extern]
 pub fn create_product(product: Product) -> ExternResult<Record> {
+    let product = with_latest_producer_hash(product)?;
     let product_hash = create_entry(&EntryTypes::Product(product.clone()))?;
 
     create_link(
@@ -27,16 +43,10 @@ pub fn create_product(product: Product) -> ExternResult<Record> {
         WasmErrorInner::Guest("Could not find the newly created Product".to_string())
     ))?;
 
-    for category in product.categories {
-        let path = category_path(category)?;
-        path.ensure()?;
-        create_link(
-            path.path_entry_hash()?,
-            product_hash.clone(),
-            LinkTypes::CategoryToProduct,
-            (),
-        )?;
-    }
+    index_product_name(product_hash.clone(), &product.name)?;
+
+    link_product_to_categories(&product_hash, &normalize_categories(&product.categories))?;
+
     Ok(record)
 }
 
@@ -124,8 +134,14 @@ pub struct UpdateProductInput {
 
 #[hdk_extern]
 pub fn update_product(input: UpdateProductInput) -> ExternResult<Record> {
-    let updated_product_hash =
-        update_entry(input.previous_product_hash.clone(), &input.updated_product)?;
+    let previous_record =
+        get(input.previous_product_hash.clone(), GetOptions::default())?.ok_or(wasm_error!(
+            WasmErrorInner::Guest("Could not find the previous Product".to_string())
+        ))?;
+    let previous_product = Product::try_from(previous_record)?;
+    let updated_product = with_latest_producer_hash(updated_product)?;
+
+    let updated_product_hash = update_entry(input.previous_product_hash.clone(), &updated_product)?;
 
     create_link(
         input.original_product_hash.clone(),
@@ -138,6 +154,28 @@ pub fn update_product(input: UpdateProductInput) -> ExternResult<Record> {
         WasmErrorInner::Guest("Could not find the newly updated Product".to_string())
     ))?;
 
+    if previous_product.name.ne(&updated_product.name) {
+        unindex_product_name(&input.original_product_hash, &previous_product.name)?;
+        index_product_name(input.original_product_hash.clone(), &updated_product.name)?;
+    }
+
+    let previous_categories = normalize_categories(&previous_product.categories);
+    let updated_categories = normalize_categories(&updated_product.categories);
+    unlink_product_from_categories(
+        &input.original_product_hash,
+        &previous_categories
+            .difference(&updated_categories)
+            .cloned()
+            .collect(),
+    )?;
+    link_product_to_categories(
+        &input.original_product_hash,
+        &updated_categories
+            .difference(&previous_categories)
+            .cloned()
+            .collect(),
+    )?;
+
     Ok(record)
 }
 
@@ -176,6 +214,21 @@ pub fn delete_product(original_product_hash: ActionHash) -> ExternResult<ActionH
         }
     }
 
+    let latest_record = get_latest_product(original_product_hash.clone())?.ok_or(wasm_error!(
+        WasmErrorInner::Guest("Could not find the latest Product".to_string())
+    ))?;
+    let latest_product = Product::try_from(latest_record)?;
+    unindex_product_name(&original_product_hash, &latest_product.name)?;
+
+    // Older revisions may have left links behind in categories the product is no longer in
+    let mut categories = BTreeSet::new();
+    for revision in get_all_revisions_for_product(original_product_hash.clone())? {
+        categories.extend(normalize_categories(
+            &Product::try_from(revision)?.categories,
+        ));
+    }
+    unlink_product_from_categories(&original_product_hash, &categories)?;
+
     delete_entry(original_product_hash)
 }
 
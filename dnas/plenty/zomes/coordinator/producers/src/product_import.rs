@@ -0,0 +1,112 @@
+use std::collections::BTreeMap;
+
+use hdk::prelude::*;
+use producers_integrity::*;
+
+use crate::product::{
+    create_product, get_latest_product, get_products_for_producer, update_product,
+    UpdateProductInput,
+};
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ImportProductsInput {
+    pub producer_hash: ActionHash,
+    pub products: Vec<Product>,
+}
+
+/// Original product hashes of the imported products
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct ImportProductsOutput {
+    pub created: Vec<ActionHash>,
+    pub updated: Vec<ActionHash>,
+    /// Products that were already in the catalog exactly as imported
+    pub unchanged: Vec<ActionHash>,
+}
+
+struct ExistingProduct {
+    original_product_hash: ActionHash,
+    latest_product_hash: ActionHash,
+    product: Product,
+}
+
+/// Latest revision of each product of the producer, by product_id
+fn get_existing_products(
+    producer_hash: ActionHash,
+) -> ExternResult<BTreeMap<String, ExistingProduct>> {
+    let mut existing_products: BTreeMap<String, ExistingProduct> = BTreeMap::new();
+    for link in get_products_for_producer(producer_hash)? {
+        let original_product_hash =
+            link.target
+                .into_action_hash()
+                .ok_or(wasm_error!(WasmErrorInner::Guest(
+                    "No action hash associated with link".to_string()
+                )))?;
+        let Some(record) = get_latest_product(original_product_hash.clone())? else {
+            continue;
+        };
+        let latest_product_hash = record.action_address().clone();
+        let product = Product::try_from(record)?;
+        existing_products.insert(
+            product.product_id.clone(),
+            ExistingProduct {
+                original_product_hash,
+                latest_product_hash,
+                product,
+            },
+        );
+    }
+    Ok(existing_products)
+}
+
+/// Imports a catalog of products for the given producer, updating the products that
+/// already exist with the same product_id instead of creating duplicates
+///
+/// Everything is committed in this same call, so if any product is invalid none are imported
+#[hdk_extern]
+pub fn import_products(input: ImportProductsInput) -> ExternResult<ImportProductsOutput> {
+    let mut existing_products = get_existing_products(input.producer_hash.clone())?;
+
+    let mut output = ImportProductsOutput::default();
+    let mut imported_product_ids: Vec<String> = Vec::new();
+    for product in input.products {
+        if product.producer_hash.ne(&input.producer_hash) {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "Product {} doesn't belong to the producer being imported",
+                product.product_id
+            ))));
+        }
+        if imported_product_ids.contains(&product.product_id) {
+            return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                "Product ID {} is repeated in the imported products",
+                product.product_id
+            ))));
+        }
+        imported_product_ids.push(product.product_id.clone());
+
+        match existing_products.remove(&product.product_id) {
+            // The producer revision gets set when committing, it's not part of the catalog
+            Some(existing)
+                if existing.product.eq(&Product {
+                    latest_producer_hash: existing.product.latest_producer_hash.clone(),
+                    ..product.clone()
+                }) =>
+            {
+                output.unchanged.push(existing.original_product_hash);
+            }
+            Some(existing) => {
+                update_product(UpdateProductInput {
+                    original_product_hash: existing.original_product_hash.clone(),
+                    previous_product_hash: existing.latest_product_hash,
+                    updated_product: product,
+                })?;
+                output.updated.push(existing.original_product_hash);
+            }
+            None => {
+                let record = create_product(product)?;
+                output.created.push(record.action_address().clone());
+            }
+        }
+    }
+
+    Ok(output)
+}
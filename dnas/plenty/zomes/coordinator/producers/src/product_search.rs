@@ -0,0 +1,127 @@
+use std::collections::BTreeSet;
+
+use hdk::prelude::*;
+use producers_integrity::*;
+
+use crate::all_producers::get_all_producers;
+use crate::product::{get_latest_product, get_products_for_producer};
+
+/// Links the product from the paths of the prefixes of each of the words in its name
+pub fn index_product_name(original_product_hash: ActionHash, name: &str) -> ExternResult<()> {
+    for token in product_name_tokens(name) {
+        for prefix in product_name_token_prefixes(&token) {
+            create_link(
+                product_names_path(&prefix).path_entry_hash()?,
+                original_product_hash.clone(),
+                LinkTypes::ProductNamesIndex,
+                product_name_tag(&token),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+pub fn unindex_product_name(original_product_hash: &ActionHash, name: &str) -> ExternResult<()> {
+    for token in product_name_tokens(name) {
+        for prefix in product_name_token_prefixes(&token) {
+            let links = get_links(
+                GetLinksInputBuilder::try_new(
+                    product_names_path(&prefix).path_entry_hash()?,
+                    LinkTypes::ProductNamesIndex,
+                )?
+                .tag_prefix(product_name_tag(&token))
+                .build(),
+            )?;
+            for link in links {
+                if product_name_token(&link.tag).as_deref() != Some(token.as_str()) {
+                    continue;
+                }
+                if let Some(action_hash) = link.target.into_action_hash() {
+                    if action_hash.eq(original_product_hash) {
+                        delete_link(link.create_link_hash)?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the latest revision of the products with a word in their name
+/// starting with each of the words in the query, e.g. "bro ric" finds "Brown rice"
+#[hdk_extern]
+pub fn search_products(query: String) -> ExternResult<Vec<Record>> {
+    let query_tokens = product_name_tokens(&query);
+    if query_tokens.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let inputs = query_tokens
+        .iter()
+        .map(|query_token| {
+            Ok(GetLinksInputBuilder::try_new(
+                product_names_path(&product_name_query_prefix(query_token)).path_entry_hash()?,
+                LinkTypes::ProductNamesIndex,
+            )?
+            .build())
+        })
+        .collect::<ExternResult<Vec<GetLinksInput>>>()?;
+    let links_by_query_token = HDK.with(|hdk| hdk.borrow().get_links(inputs))?;
+
+    // Products matching all the words in the query
+    let mut matching_products: Option<BTreeSet<ActionHashB64>> = None;
+    for (query_token, links) in query_tokens.iter().zip(links_by_query_token) {
+        let products: BTreeSet<ActionHashB64> = links
+            .into_iter()
+            .filter(|link| {
+                product_name_token(&link.tag)
+                    .is_some_and(|token| token.starts_with(query_token.as_str()))
+            })
+            .filter_map(|link| link.target.into_action_hash())
+            .map(ActionHashB64::from)
+            .collect();
+        matching_products = Some(match matching_products {
+            Some(matching_products) => matching_products.intersection(&products).cloned().collect(),
+            None => products,
+        });
+    }
+
+    let mut records: Vec<Record> = Vec::new();
+    for original_product_hash in matching_products.unwrap_or_default() {
+        if let Some(record) = get_latest_product(original_product_hash.into())? {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+/// Indexes the names of the products that were created before the name index existed
+///
+/// Returns the number of products indexed. Needs to be run by an order manager,
+/// as it indexes products from all producers
+#[hdk_extern]
+pub fn migrate_product_names_index() -> ExternResult<u32> {
+    let mut indexed: BTreeSet<ActionHashB64> = BTreeSet::new();
+    for producer_link in get_all_producers()? {
+        let Some(producer_hash) = producer_link.target.into_action_hash() else {
+            continue;
+        };
+        for product_link in get_products_for_producer(producer_hash)? {
+            let Some(original_product_hash) = product_link.target.into_action_hash() else {
+                continue;
+            };
+            if indexed.contains(&original_product_hash.clone().into()) {
+                continue;
+            }
+            let Some(record) = get_latest_product(original_product_hash.clone())? else {
+                continue;
+            };
+            let product = Product::try_from(record)?;
+            // Remove the links from previous runs, so that they are not duplicated
+            unindex_product_name(&original_product_hash, &product.name)?;
+            index_product_name(original_product_hash.clone(), &product.name)?;
+            indexed.insert(original_product_hash.into());
+        }
+    }
+    Ok(indexed.len() as u32)
+}
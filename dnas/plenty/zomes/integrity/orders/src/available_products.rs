@@ -286,3 +286,60 @@ pub fn validate_delete_link_available_products_updates(
         "AvailableProductsUpdates links cannot be deleted",
     )))
 }
+
+/// Indexes the orders that offered a product, so that they can be found from the product
+pub fn validate_create_link_product_to_available_products(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let product_hash =
+        base_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let available_products: crate::AvailableProducts = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let ProducerAvailability::Available {
+        available_products: products,
+    } = &available_products.producer_availability
+    else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "ProductToAvailableProducts links can only target available products",
+        )));
+    };
+    if !products.contains(&product_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "ProductToAvailableProducts links can only have as the base a product that is offered in their target",
+        )));
+    }
+    validate_can_change_available_products(&action.author, &action_hash, available_products)
+}
+
+pub fn validate_delete_link_product_to_available_products(
+    _action_hash: ActionHash,
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "ProductToAvailableProducts links cannot be deleted",
+    )))
+}
@@ -1,5 +1,11 @@
+use std::collections::HashSet;
+
 use hdi::prelude::*;
 use households_types::*;
+use producers_types::{is_revision_of, Product};
+use roles_types::validate_agent_had_undeleted_role_claim_at_the_time;
+
+use crate::roles::{ORDER_MANAGER, ROLES_INTEGRITY_ZOME_NAME};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct ProductOrder {
@@ -12,10 +18,218 @@ pub struct ProductOrder {
 #[hdk_entry_helper]
 pub struct HouseholdOrder {
     pub order_hash: ActionHash,
+    pub latest_order_hash: ActionHash,
     pub household_hash: ActionHash,
     pub products: Vec<ProductOrder>,
 }
 
+/// Household orders can only be created, updated or deleted while their order is Open and
+/// before its deadline
+///
+/// The order status is read from `latest_order_hash`, which must be a revision of `order_hash`.
+/// The latest revision of the order can't be resolved deterministically, but the deadline of an
+/// Open order can only be extended, and the order can only be closed after it (see
+/// `validate_order_status_transition`), so the deadline of any Open revision an author cites is
+/// never later than the moment the order was closed
+pub fn validate_order_is_open(
+    timestamp: &Timestamp,
+    household_order: &HouseholdOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(household_order.latest_order_hash.clone())?;
+    if household_order
+        .latest_order_hash
+        .ne(&household_order.order_hash)
+    {
+        let Action::Update(update) = record.action() else {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The latest_order_hash for a HouseholdOrder must be a revision of its order",
+            )));
+        };
+        if crate::original_order_hash(update)?.ne(&household_order.order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The latest_order_hash for a HouseholdOrder must be a revision of its order",
+            )));
+        }
+    }
+    let latest_order = crate::Order::try_from(record)?;
+
+    let is_open = match latest_order.status {
+        crate::OrderStatus::Open { deadline, .. } => timestamp.lt(&deadline),
+        _ => false,
+    };
+    if !is_open {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Household orders can only be changed while the order is open and before its deadline",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Whether the author was an order manager when committing the given action
+fn was_order_manager(author: &AgentPubKey, action_hash: &ActionHash) -> ExternResult<bool> {
+    let was_order_manager = validate_agent_had_undeleted_role_claim_at_the_time(
+        author,
+        action_hash,
+        &String::from(ORDER_MANAGER),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+    Ok(matches!(was_order_manager, ValidateCallbackResult::Valid))
+}
+
+/// Members of the household can change its household orders while the order is open,
+/// and order managers can change any household order at any time to make late corrections,
+/// so their role is checked before the membership of the household
+fn validate_can_change_household_order(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+    timestamp: &Timestamp,
+    household_order: &HouseholdOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    if was_order_manager(author, action_hash)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+        author.clone(),
+        action_hash.clone(),
+        household_order.household_hash.clone(),
+    )?;
+    let ValidateCallbackResult::Valid = member_of_household else {
+        return Ok(member_of_household);
+    };
+
+    validate_order_is_open(timestamp, household_order)
+}
+
+/// Links to household orders can be created and deleted by the members of the household,
+/// and by order managers making late corrections
+fn validate_was_member_or_order_manager(
+    author: AgentPubKey,
+    action_hash: ActionHash,
+    household_hash: ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    if was_order_manager(&author, &action_hash)? {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    validate_agent_was_member_of_household_at_the_time(author, action_hash, household_hash)
+}
+
+/// Whether the given household order revision was committed before the deadline,
+/// or by an order manager making a late correction
+pub fn is_household_order_within_deadline(
+    household_order_hash: &ActionHash,
+    deadline: &Timestamp,
+) -> ExternResult<bool> {
+    let action = must_get_action(household_order_hash.clone())?;
+    if action.action().timestamp().lt(deadline) {
+        return Ok(true);
+    }
+    was_order_manager(action.action().author(), household_order_hash)
+}
+
+/// Returns the original hashes of the products offered in the order, from the AvailableProducts
+/// snapshotted in the most recent Open status up to the given revision of the order
+///
+/// The OrderToAvailableProducts links can't be read deterministically, so the Open status
+/// is the only record of which products were offered
+fn offered_products(order_revision_hash: &ActionHash) -> ExternResult<Option<Vec<ActionHash>>> {
+    let mut order_revision_hash = order_revision_hash.clone();
+    loop {
+        let record = must_get_valid_record(order_revision_hash)?;
+        let previous_revision_hash = match record.action() {
+            Action::Update(update) => Some(update.original_action_address.clone()),
+            _ => None,
+        };
+        let order = crate::Order::try_from(record)?;
+        if let crate::OrderStatus::Open {
+            available_products, ..
+        } = order.status
+        {
+            let mut products: Vec<ActionHash> = Vec::new();
+            for available_products_hash in available_products {
+                let record = must_get_valid_record(available_products_hash)?;
+                let available_products = crate::AvailableProducts::try_from(record)?;
+                if let crate::ProducerAvailability::Available { available_products } =
+                    available_products.producer_availability
+                {
+                    products.extend(available_products);
+                }
+            }
+            return Ok(Some(products));
+        }
+        let Some(previous_revision_hash) = previous_revision_hash else {
+            return Ok(None);
+        };
+        order_revision_hash = previous_revision_hash;
+    }
+}
+
+/// Every product line must be for a product offered in the order, with a positive amount,
+/// and a product can only appear once in each household order
+pub fn validate_product_orders_are_offered(
+    household_order: &HouseholdOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    if household_order.products.is_empty() {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let Some(offered_products) = offered_products(&household_order.latest_order_hash)? else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't order products from an order that hasn't been opened",
+        )));
+    };
+
+    let mut ordered_products: HashSet<ActionHash> = HashSet::new();
+    for product_order in &household_order.products {
+        if product_order.amount == 0 {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The amount ordered of each product must be greater than 0",
+            )));
+        }
+        if !ordered_products.insert(product_order.original_product_hash.clone()) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "A product can only be ordered once in each household order",
+            )));
+        }
+        if !offered_products.contains(&product_order.original_product_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Can't order a product that is not offered in this order",
+            )));
+        }
+        if !is_revision_of(
+            &product_order.ordered_product_hash,
+            &product_order.original_product_hash,
+        )? {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The ordered_product_hash must be a revision of the original_product_hash",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// No single household can order more units of a product than the maximum_available
+/// of the revision it ordered
+///
+/// The cap across all households can't be checked here, since other households' orders
+/// can't be read deterministically: the coordinator checks it and flags oversubscriptions
+pub fn validate_product_orders_within_maximum_available(
+    household_order: &HouseholdOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    for product_order in &household_order.products {
+        let record = must_get_valid_record(product_order.ordered_product_hash.clone())?;
+        let product = Product::try_from(record)?;
+        if let Some(maximum_available) = product.maximum_available {
+            if product_order.amount > maximum_available {
+                return Ok(ValidateCallbackResult::Invalid(format!(
+                    "Can't order {} units of {}: only {} are available",
+                    product_order.amount, product.name, maximum_available
+                )));
+            }
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
 pub fn validate_create_household_order(
     action_hash: ActionHash,
     action: EntryCreationAction,
@@ -38,14 +252,25 @@ pub fn validate_create_household_order(
             "Dependant action must be accompanied by an entry"
         ))))?;
 
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
-        action.author().clone(),
-        action_hash,
-        household_order.household_hash,
+    let can_change = validate_can_change_household_order(
+        action.author(),
+        &action_hash,
+        action.timestamp(),
+        &household_order,
     )?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
 
-    let ValidateCallbackResult::Valid = member_of_household else {
-        return Ok(member_of_household);
+    let products_are_offered = validate_product_orders_are_offered(&household_order)?;
+    let ValidateCallbackResult::Valid = products_are_offered else {
+        return Ok(products_are_offered);
+    };
+
+    let within_maximum_available =
+        validate_product_orders_within_maximum_available(&household_order)?;
+    let ValidateCallbackResult::Valid = within_maximum_available else {
+        return Ok(within_maximum_available);
     };
     Ok(ValidateCallbackResult::Valid)
 }
@@ -75,14 +300,36 @@ pub fn validate_update_household_order(
         )));
     }
 
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
-        action.author,
-        action_hash,
-        household_order.household_hash,
+    // Authors can't cite an older revision of the order than the one they are updating,
+    // to get back to an Open revision with a later deadline
+    if !is_revision_of(
+        &household_order.latest_order_hash,
+        &original_household_order.latest_order_hash,
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The latest_order_hash of a HouseholdOrder can't go back to an older revision of its order",
+        )));
+    }
+
+    let can_change = validate_can_change_household_order(
+        &action.author,
+        &action_hash,
+        &action.timestamp,
+        &household_order,
     )?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
 
-    let ValidateCallbackResult::Valid = member_of_household else {
-        return Ok(member_of_household);
+    let products_are_offered = validate_product_orders_are_offered(&household_order)?;
+    let ValidateCallbackResult::Valid = products_are_offered else {
+        return Ok(products_are_offered);
+    };
+
+    let within_maximum_available =
+        validate_product_orders_within_maximum_available(&household_order)?;
+    let ValidateCallbackResult::Valid = within_maximum_available else {
+        return Ok(within_maximum_available);
     };
     Ok(ValidateCallbackResult::Valid)
 }
@@ -93,14 +340,14 @@ pub fn validate_delete_household_order(
     _original_action: EntryCreationAction,
     original_household_order: HouseholdOrder,
 ) -> ExternResult<ValidateCallbackResult> {
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
-        action.author,
-        action_hash,
-        original_household_order.household_hash,
+    let can_change = validate_can_change_household_order(
+        &action.author,
+        &action_hash,
+        &action.timestamp,
+        &original_household_order,
     )?;
-
-    let ValidateCallbackResult::Valid = member_of_household else {
-        return Ok(member_of_household);
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
     };
     Ok(ValidateCallbackResult::Valid)
 }
@@ -140,7 +387,7 @@ pub fn validate_create_link_order_to_household_orders(
             "Linked action must reference an entry".to_string()
         )))?;
 
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+    let member_of_household = validate_was_member_or_order_manager(
         action.author,
         action_hash,
         household_order.household_hash,
@@ -174,7 +421,7 @@ pub fn validate_delete_link_order_to_household_orders(
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+    let member_of_household = validate_was_member_or_order_manager(
         action.author,
         action_hash,
         household_order.household_hash,
@@ -225,7 +472,7 @@ pub fn validate_create_link_household_to_household_orders(
         return Ok(ValidateCallbackResult::Invalid(String::from("HouseholdToHouseholdOrders links can only have as the base the household hash specified in the HouseholdOrder entry")));
     }
 
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+    let member_of_household = validate_was_member_or_order_manager(
         action.author,
         action_hash,
         household_order.household_hash,
@@ -250,11 +497,8 @@ pub fn validate_delete_link_household_to_household_orders(
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "No action hash associated with link".to_string()
         )))?;
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
-        action.author,
-        action_hash,
-        household_hash,
-    )?;
+    let member_of_household =
+        validate_was_member_or_order_manager(action.author, action_hash, household_hash)?;
 
     let ValidateCallbackResult::Valid = member_of_household else {
         return Ok(member_of_household);
@@ -300,7 +544,7 @@ pub fn validate_create_link_household_order_updates(
         )))?;
     // TODO: add the appropriate validation rules
 
-    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+    let member_of_household = validate_was_member_or_order_manager(
         action.author,
         action_hash,
         household_order.household_hash,
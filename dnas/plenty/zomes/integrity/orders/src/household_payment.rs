@@ -0,0 +1,260 @@
+use hdi::prelude::*;
+use households_types::*;
+use roles_types::*;
+
+use crate::roles::{BOOKKEEPER_ROLE, ROLES_INTEGRITY_ZOME_NAME};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum PaymentMethod {
+    Cash,
+    BankTransfer,
+    Other { description: String },
+}
+
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct HouseholdPayment {
+    pub order_hash: ActionHash,
+    pub household_hash: ActionHash,
+    pub amount_cents: u32,
+    pub payment_method: PaymentMethod,
+}
+
+pub fn validate_create_household_payment(
+    action_hash: ActionHash,
+    action: EntryCreationAction,
+    household_payment: HouseholdPayment,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(household_payment.order_hash.clone())?;
+    let _order: crate::Order = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+    let record = must_get_valid_record(household_payment.household_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        action.author(),
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_update_household_payment(
+    action_hash: ActionHash,
+    action: Update,
+    household_payment: HouseholdPayment,
+    _original_action: EntryCreationAction,
+    original_household_payment: HouseholdPayment,
+) -> ExternResult<ValidateCallbackResult> {
+    if household_payment
+        .order_hash
+        .ne(&original_household_payment.order_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the order_hash for a HouseholdPayment",
+        )));
+    }
+
+    if household_payment
+        .household_hash
+        .ne(&original_household_payment.household_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the household_hash for a HouseholdPayment",
+        )));
+    }
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_household_payment(
+    action_hash: ActionHash,
+    action: Delete,
+    _original_action: EntryCreationAction,
+    _original_household_payment: HouseholdPayment,
+) -> ExternResult<ValidateCallbackResult> {
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_order_to_household_payments(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash.clone())?;
+    let _order: crate::Order = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let household_payment: crate::HouseholdPayment = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+
+    if household_payment.order_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("OrderToHouseholdPayments links can only have as the base the order hash specified in the HouseholdPayment entry")));
+    }
+
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_order_to_household_payments(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_link_household_to_household_payments(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let household_payment: crate::HouseholdPayment = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+
+    if household_payment.household_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("HouseholdToHouseholdPayments links can only have as the base the household hash specified in the HouseholdPayment entry")));
+    }
+
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_delete_link_household_to_household_payments(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let was_bookkeeper = validate_agent_had_undeleted_role_claim_at_the_time(
+        &action.author,
+        &action_hash,
+        &String::from(BOOKKEEPER_ROLE),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+
+    let ValidateCallbackResult::Valid = was_bookkeeper else {
+        return Ok(was_bookkeeper);
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
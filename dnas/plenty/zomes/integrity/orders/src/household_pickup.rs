@@ -0,0 +1,308 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+use households_types::*;
+use roles_types::*;
+
+use crate::roles::{ORDER_MANAGER, ROLES_INTEGRITY_ZOME_NAME};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum PickupExceptionKind {
+    Missing,
+    Damaged,
+    Other { description: String },
+}
+
+/// Something that was not right with one of the products in the household's bag
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PickupException {
+    pub original_product_hash: ActionHash,
+    /// Number of packages affected
+    pub amount: u32,
+    pub kind: PickupExceptionKind,
+    pub comment: Option<String>,
+}
+
+/// Records that a household collected its goods on distribution day
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct HouseholdPickup {
+    pub order_hash: ActionHash,
+    pub household_hash: ActionHash,
+    pub picked_up_at: Timestamp,
+    pub handed_out_by: AgentPubKey,
+    pub exceptions: Vec<PickupException>,
+}
+
+/// Only the members of the household or the order managers can record its pickups
+fn validate_can_change_household_pickup(
+    author: AgentPubKey,
+    action_hash: ActionHash,
+    household_hash: ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    let was_order_manager = validate_agent_had_undeleted_role_claim_at_the_time(
+        &author,
+        &action_hash,
+        &String::from(ORDER_MANAGER),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+    let ValidateCallbackResult::Valid = was_order_manager else {
+        return validate_agent_was_member_of_household_at_the_time(
+            author,
+            action_hash,
+            household_hash,
+        );
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Whoever records the pickup is the one that handed out the goods
+fn validate_handed_out_by(
+    author: &AgentPubKey,
+    household_pickup: &HouseholdPickup,
+) -> ExternResult<ValidateCallbackResult> {
+    if household_pickup.handed_out_by.ne(author) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The handed_out_by of a HouseholdPickup must be the agent recording it",
+        )));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_pickup_exceptions(
+    household_pickup: &HouseholdPickup,
+) -> ExternResult<ValidateCallbackResult> {
+    let mut exceptions: HashSet<(ActionHash, String)> = HashSet::new();
+    for exception in &household_pickup.exceptions {
+        if exception.amount == 0 {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The amount of each pickup exception must be greater than 0",
+            )));
+        }
+        let kind = match &exception.kind {
+            PickupExceptionKind::Missing => String::from("Missing"),
+            PickupExceptionKind::Damaged => String::from("Damaged"),
+            PickupExceptionKind::Other { description } => {
+                if description.trim().is_empty() {
+                    return Ok(ValidateCallbackResult::Invalid(String::from(
+                        "Other pickup exceptions must have a description",
+                    )));
+                }
+                format!("Other: {description}")
+            }
+        };
+        if !exceptions.insert((exception.original_product_hash.clone(), kind)) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "A product can only have one pickup exception of each kind",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_household_pickup(
+    action_hash: ActionHash,
+    action: EntryCreationAction,
+    household_pickup: HouseholdPickup,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(household_pickup.order_hash.clone())?;
+    let _order: crate::Order = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+    let record = must_get_valid_record(household_pickup.household_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+
+    let can_change = validate_can_change_household_pickup(
+        action.author().clone(),
+        action_hash,
+        household_pickup.household_hash.clone(),
+    )?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
+
+    let handed_out_by = validate_handed_out_by(action.author(), &household_pickup)?;
+    let ValidateCallbackResult::Valid = handed_out_by else {
+        return Ok(handed_out_by);
+    };
+
+    validate_pickup_exceptions(&household_pickup)
+}
+
+pub fn validate_update_household_pickup(
+    action_hash: ActionHash,
+    action: Update,
+    household_pickup: HouseholdPickup,
+    _original_action: EntryCreationAction,
+    original_household_pickup: HouseholdPickup,
+) -> ExternResult<ValidateCallbackResult> {
+    if household_pickup
+        .order_hash
+        .ne(&original_household_pickup.order_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the order_hash for a HouseholdPickup",
+        )));
+    }
+
+    if household_pickup
+        .household_hash
+        .ne(&original_household_pickup.household_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the household_hash for a HouseholdPickup",
+        )));
+    }
+
+    let can_change = validate_can_change_household_pickup(
+        action.author.clone(),
+        action_hash,
+        household_pickup.household_hash.clone(),
+    )?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
+
+    let handed_out_by = validate_handed_out_by(&action.author, &household_pickup)?;
+    let ValidateCallbackResult::Valid = handed_out_by else {
+        return Ok(handed_out_by);
+    };
+
+    validate_pickup_exceptions(&household_pickup)
+}
+
+pub fn validate_delete_household_pickup(
+    action_hash: ActionHash,
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_household_pickup: HouseholdPickup,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_can_change_household_pickup(
+        action.author,
+        action_hash,
+        original_household_pickup.household_hash,
+    )
+}
+
+fn household_pickup_from_target(target_address: AnyLinkableHash) -> ExternResult<HouseholdPickup> {
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let household_pickup: crate::HouseholdPickup = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    Ok(household_pickup)
+}
+
+pub fn validate_create_link_order_to_household_pickups(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash.clone())?;
+    let _order: crate::Order = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let household_pickup = household_pickup_from_target(target_address)?;
+
+    if household_pickup.order_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("OrderToHouseholdPickups links can only have as the base the order hash specified in the HouseholdPickup entry")));
+    }
+
+    validate_can_change_household_pickup(
+        action.author,
+        action_hash,
+        household_pickup.household_hash,
+    )
+}
+
+pub fn validate_delete_link_order_to_household_pickups(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let household_pickup = household_pickup_from_target(target)?;
+    validate_can_change_household_pickup(
+        action.author,
+        action_hash,
+        household_pickup.household_hash,
+    )
+}
+
+pub fn validate_create_link_household_to_household_pickups(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let household_pickup = household_pickup_from_target(target_address)?;
+
+    if household_pickup.household_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("HouseholdToHouseholdPickups links can only have as the base the household hash specified in the HouseholdPickup entry")));
+    }
+
+    validate_can_change_household_pickup(action.author, action_hash, base_hash)
+}
+
+pub fn validate_delete_link_household_to_household_pickups(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let household_hash = base
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    validate_can_change_household_pickup(action.author, action_hash, household_hash)
+}
@@ -11,6 +11,12 @@ pub mod order;
 pub use order::*;
 pub mod available_products;
 pub use available_products::*;
+pub mod household_payment;
+pub use household_payment::*;
+pub mod standing_order;
+pub use standing_order::*;
+pub mod household_pickup;
+pub use household_pickup::*;
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -22,6 +28,9 @@ pub enum EntryTypes {
     ProducerDelivery(ProducerDelivery),
     ProducerInvoice(ProducerInvoice),
     AvailableProducts(AvailableProducts),
+    HouseholdPayment(HouseholdPayment),
+    StandingOrder(StandingOrder),
+    HouseholdPickup(HouseholdPickup),
 }
 
 #[derive(Serialize, Deserialize)]
@@ -38,6 +47,15 @@ pub enum LinkTypes {
     AllOrders,
     OrderToAvailableProducts,
     AvailableProductsUpdates,
+    OrderToHouseholdPayments,
+    HouseholdToHouseholdPayments,
+    HouseholdToStandingOrders,
+    StandingOrderUpdates,
+    OrdersByMonth,
+    OrdersByStatus,
+    OrderToHouseholdPickups,
+    HouseholdToHouseholdPickups,
+    ProductToAvailableProducts,
 }
 
 #[hdk_extern]
@@ -81,6 +99,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 ),
                 EntryTypes::ProducerDelivery(producer_delivery) => {
                     validate_create_producer_delivery(
+                        action_hash(&op).clone(),
                         EntryCreationAction::Create(action),
                         producer_delivery,
                     )
@@ -97,6 +116,23 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         available_products,
                     )
                 }
+                EntryTypes::HouseholdPayment(household_payment) => {
+                    validate_create_household_payment(
+                        action_hash(&op).clone(),
+                        EntryCreationAction::Create(action),
+                        household_payment,
+                    )
+                }
+                EntryTypes::StandingOrder(standing_order) => validate_create_standing_order(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    standing_order,
+                ),
+                EntryTypes::HouseholdPickup(household_pickup) => validate_create_household_pickup(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    household_pickup,
+                ),
             },
             OpEntry::UpdateEntry {
                 app_entry, action, ..
@@ -113,6 +149,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 ),
                 EntryTypes::ProducerDelivery(producer_delivery) => {
                     validate_create_producer_delivery(
+                        action_hash(&op).clone(),
                         EntryCreationAction::Update(action),
                         producer_delivery,
                     )
@@ -129,6 +166,23 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         available_products,
                     )
                 }
+                EntryTypes::HouseholdPayment(household_payment) => {
+                    validate_create_household_payment(
+                        action_hash(&op).clone(),
+                        EntryCreationAction::Update(action),
+                        household_payment,
+                    )
+                }
+                EntryTypes::StandingOrder(standing_order) => validate_create_standing_order(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Update(action),
+                    standing_order,
+                ),
+                EntryTypes::HouseholdPickup(household_pickup) => validate_create_household_pickup(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Update(action),
+                    household_pickup,
+                ),
             },
             _ => Ok(ValidateCallbackResult::Valid),
         },
@@ -199,6 +253,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 }
                             };
                         validate_update_producer_delivery(
+                            action_hash(&op).clone(),
                             action,
                             producer_delivery,
                             original_create_action,
@@ -225,6 +280,66 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             original_household_order,
                         )
                     }
+                    EntryTypes::HouseholdPayment(household_payment) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_household_payment =
+                            match HouseholdPayment::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get HouseholdPayment from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_household_payment(
+                            action_hash(&op).clone(),
+                            action,
+                            household_payment,
+                            original_create_action,
+                            original_household_payment,
+                        )
+                    }
+                    EntryTypes::StandingOrder(standing_order) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_standing_order =
+                            match StandingOrder::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get StandingOrder from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_standing_order(
+                            action_hash(&op).clone(),
+                            action,
+                            standing_order,
+                            original_create_action,
+                            original_standing_order,
+                        )
+                    }
+                    EntryTypes::HouseholdPickup(household_pickup) => {
+                        let original_app_entry =
+                            must_get_valid_record(action.clone().original_action_address)?;
+                        let original_household_pickup =
+                            match HouseholdPickup::try_from(original_app_entry) {
+                                Ok(entry) => entry,
+                                Err(e) => {
+                                    return Ok(ValidateCallbackResult::Invalid(format!(
+                                        "Expected to get HouseholdPickup from Record: {e:?}"
+                                    )));
+                                }
+                            };
+                        validate_update_household_pickup(
+                            action_hash(&op).clone(),
+                            action,
+                            household_pickup,
+                            original_create_action,
+                            original_household_pickup,
+                        )
+                    }
                     EntryTypes::Order(order) => {
                         let original_app_entry =
                             must_get_valid_record(action.clone().original_action_address)?;
@@ -236,7 +351,13 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 )));
                             }
                         };
-                        validate_update_order(action, order, original_create_action, original_order)
+                        validate_update_order(
+                            action_hash(&op),
+                            action,
+                            order,
+                            original_create_action,
+                            original_order,
+                        )
                     }
                 }
             }
@@ -300,6 +421,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 }
                 EntryTypes::ProducerDelivery(original_producer_delivery) => {
                     validate_delete_producer_delivery(
+                        action_hash(&op).clone(),
                         delete_entry.clone().action,
                         original_action,
                         original_producer_delivery,
@@ -319,6 +441,30 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     original_action,
                     original_order,
                 ),
+                EntryTypes::HouseholdPayment(original_household_payment) => {
+                    validate_delete_household_payment(
+                        action_hash(&op).clone(),
+                        delete_entry.clone().action,
+                        original_action,
+                        original_household_payment,
+                    )
+                }
+                EntryTypes::StandingOrder(original_standing_order) => {
+                    validate_delete_standing_order(
+                        action_hash(&op).clone(),
+                        delete_entry.clone().action,
+                        original_action,
+                        original_standing_order,
+                    )
+                }
+                EntryTypes::HouseholdPickup(original_household_pickup) => {
+                    validate_delete_household_pickup(
+                        action_hash(&op).clone(),
+                        delete_entry.clone().action,
+                        original_action,
+                        original_household_pickup,
+                    )
+                }
             }
         }
         FlatOp::RegisterCreateLink {
@@ -354,8 +500,16 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::StandingOrderUpdates => validate_create_link_standing_order_updates(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::OrderToProducerDeliveries => {
                 validate_create_link_order_to_producer_deliveries(
+                    action_hash(&op).clone(),
                     action,
                     base_address,
                     target_address,
@@ -364,6 +518,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             }
             LinkTypes::ProducerToProducerDeliveries => {
                 validate_create_link_producer_to_producer_deliveries(
+                    action_hash(&op).clone(),
                     action,
                     base_address,
                     target_address,
@@ -389,6 +544,20 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             LinkTypes::AllOrders => {
                 validate_create_link_all_orders(action, base_address, target_address, tag)
             }
+            LinkTypes::OrdersByMonth => validate_create_link_orders_by_month(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::OrdersByStatus => validate_create_link_orders_by_status(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::OrderToAvailableProducts => {
                 validate_create_link_order_to_available_products(
                     action_hash(&op).clone(),
@@ -405,6 +574,58 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::OrderToHouseholdPayments => {
+                validate_create_link_order_to_household_payments(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::HouseholdToHouseholdPayments => {
+                validate_create_link_household_to_household_payments(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::HouseholdToStandingOrders => {
+                validate_create_link_household_to_standing_orders(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::OrderToHouseholdPickups => validate_create_link_order_to_household_pickups(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::HouseholdToHouseholdPickups => {
+                validate_create_link_household_to_household_pickups(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::ProductToAvailableProducts => {
+                validate_create_link_product_to_available_products(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
         },
         FlatOp::RegisterDeleteLink {
             link_type,
@@ -446,8 +667,16 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::StandingOrderUpdates => validate_delete_link_standing_order_updates(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::OrderToProducerDeliveries => {
                 validate_delete_link_order_to_producer_deliveries(
+                    action_hash(&op).clone(),
                     action,
                     original_action,
                     base_address,
@@ -465,6 +694,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             ),
             LinkTypes::ProducerToProducerDeliveries => {
                 validate_delete_link_producer_to_producer_deliveries(
+                    action_hash(&op).clone(),
                     action,
                     original_action,
                     base_address,
@@ -489,6 +719,22 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::OrdersByMonth => validate_delete_link_orders_by_month(
+                action_hash(&op).clone(),
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::OrdersByStatus => validate_delete_link_orders_by_status(
+                action_hash(&op).clone(),
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::OrderToAvailableProducts => {
                 validate_delete_link_order_to_available_products(
                     action_hash(&op).clone(),
@@ -507,6 +753,64 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::OrderToHouseholdPayments => {
+                validate_delete_link_order_to_household_payments(
+                    action_hash(&op).clone(),
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::HouseholdToHouseholdPayments => {
+                validate_delete_link_household_to_household_payments(
+                    action_hash(&op).clone(),
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::HouseholdToStandingOrders => {
+                validate_delete_link_household_to_standing_orders(
+                    action_hash(&op).clone(),
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::OrderToHouseholdPickups => validate_delete_link_order_to_household_pickups(
+                action_hash(&op).clone(),
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::HouseholdToHouseholdPickups => {
+                validate_delete_link_household_to_household_pickups(
+                    action_hash(&op).clone(),
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::ProductToAvailableProducts => {
+                validate_delete_link_product_to_available_products(
+                    action_hash(&op).clone(),
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
         },
         FlatOp::StoreRecord(store_record) => match store_record {
             OpRecord::CreateEntry { app_entry, action } => match app_entry {
@@ -522,6 +826,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 ),
                 EntryTypes::ProducerDelivery(producer_delivery) => {
                     validate_create_producer_delivery(
+                        action_hash(&op).clone(),
                         EntryCreationAction::Create(action),
                         producer_delivery,
                     )
@@ -538,6 +843,23 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         available_products,
                     )
                 }
+                EntryTypes::HouseholdPayment(household_payment) => {
+                    validate_create_household_payment(
+                        action_hash(&op).clone(),
+                        EntryCreationAction::Create(action),
+                        household_payment,
+                    )
+                }
+                EntryTypes::StandingOrder(standing_order) => validate_create_standing_order(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    standing_order,
+                ),
+                EntryTypes::HouseholdPickup(household_pickup) => validate_create_household_pickup(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    household_pickup,
+                ),
             },
             OpRecord::UpdateEntry {
                 original_action_hash,
@@ -580,7 +902,13 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                         );
                                 }
                             };
-                            validate_update_order(action, order, original_action, original_order)
+                            validate_update_order(
+                                action_hash(&op),
+                                action,
+                                order,
+                                original_action,
+                                original_order,
+                            )
                         } else {
                             Ok(result)
                         }
@@ -620,6 +948,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     }
                     EntryTypes::ProducerDelivery(producer_delivery) => {
                         let result = validate_create_producer_delivery(
+                            action_hash(&op).clone(),
                             EntryCreationAction::Update(action.clone()),
                             producer_delivery.clone(),
                         )?;
@@ -641,6 +970,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 }
                             };
                             validate_update_producer_delivery(
+                                action_hash(&op).clone(),
                                 action,
                                 producer_delivery,
                                 original_action,
@@ -718,6 +1048,107 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             Ok(result)
                         }
                     }
+                    EntryTypes::HouseholdPayment(household_payment) => {
+                        let result = validate_create_household_payment(
+                            action_hash(&op).clone(),
+                            EntryCreationAction::Update(action.clone()),
+                            household_payment.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_household_payment: Option<HouseholdPayment> =
+                                original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                            let original_household_payment = match original_household_payment {
+                                Some(household_payment) => household_payment,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_household_payment(
+                                action_hash(&op).clone(),
+                                action,
+                                household_payment,
+                                original_action,
+                                original_household_payment,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::StandingOrder(standing_order) => {
+                        let result = validate_create_standing_order(
+                            action_hash(&op).clone(),
+                            EntryCreationAction::Update(action.clone()),
+                            standing_order.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_standing_order: Option<StandingOrder> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_standing_order = match original_standing_order {
+                                Some(standing_order) => standing_order,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_standing_order(
+                                action_hash(&op).clone(),
+                                action,
+                                standing_order,
+                                original_action,
+                                original_standing_order,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::HouseholdPickup(household_pickup) => {
+                        let result = validate_create_household_pickup(
+                            action_hash(&op).clone(),
+                            EntryCreationAction::Update(action.clone()),
+                            household_pickup.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_household_pickup: Option<HouseholdPickup> =
+                                original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                            let original_household_pickup = match original_household_pickup {
+                                Some(household_pickup) => household_pickup,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_household_pickup(
+                                action_hash(&op).clone(),
+                                action,
+                                household_pickup,
+                                original_action,
+                                original_household_pickup,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
                 }
             }
             OpRecord::DeleteEntry {
@@ -783,6 +1214,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     }
                     EntryTypes::ProducerDelivery(original_producer_delivery) => {
                         validate_delete_producer_delivery(
+                            action_hash(&op).clone(),
                             action,
                             original_action,
                             original_producer_delivery,
@@ -804,6 +1236,30 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             original_available_products,
                         )
                     }
+                    EntryTypes::HouseholdPayment(original_household_payment) => {
+                        validate_delete_household_payment(
+                            action_hash(&op).clone(),
+                            action,
+                            original_action,
+                            original_household_payment,
+                        )
+                    }
+                    EntryTypes::StandingOrder(original_standing_order) => {
+                        validate_delete_standing_order(
+                            action_hash(&op).clone(),
+                            action,
+                            original_action,
+                            original_standing_order,
+                        )
+                    }
+                    EntryTypes::HouseholdPickup(original_household_pickup) => {
+                        validate_delete_household_pickup(
+                            action_hash(&op).clone(),
+                            action,
+                            original_action,
+                            original_household_pickup,
+                        )
+                    }
                 }
             }
             OpRecord::CreateLink {
@@ -841,8 +1297,16 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     target_address,
                     tag,
                 ),
+                LinkTypes::StandingOrderUpdates => validate_create_link_standing_order_updates(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
                 LinkTypes::OrderToProducerDeliveries => {
                     validate_create_link_order_to_producer_deliveries(
+                        action_hash(&op).clone(),
                         action,
                         base_address,
                         target_address,
@@ -860,6 +1324,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 }
                 LinkTypes::ProducerToProducerDeliveries => {
                     validate_create_link_producer_to_producer_deliveries(
+                        action_hash(&op).clone(),
                         action,
                         base_address,
                         target_address,
@@ -878,6 +1343,20 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 LinkTypes::AllOrders => {
                     validate_create_link_all_orders(action, base_address, target_address, tag)
                 }
+                LinkTypes::OrdersByMonth => validate_create_link_orders_by_month(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
+                LinkTypes::OrdersByStatus => validate_create_link_orders_by_status(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
                 LinkTypes::OrderToAvailableProducts => {
                     validate_create_link_order_to_available_products(
                         action_hash(&op).clone(),
@@ -896,6 +1375,60 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         tag,
                     )
                 }
+                LinkTypes::OrderToHouseholdPayments => {
+                    validate_create_link_order_to_household_payments(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::HouseholdToHouseholdPayments => {
+                    validate_create_link_household_to_household_payments(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::HouseholdToStandingOrders => {
+                    validate_create_link_household_to_standing_orders(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::OrderToHouseholdPickups => {
+                    validate_create_link_order_to_household_pickups(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::HouseholdToHouseholdPickups => {
+                    validate_create_link_household_to_household_pickups(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::ProductToAvailableProducts => {
+                    validate_create_link_product_to_available_products(
+                        action_hash(&op).clone(),
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
             },
             OpRecord::DeleteLink {
                 original_action_hash,
@@ -955,8 +1488,16 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             create_link.tag,
                         )
                     }
+                    LinkTypes::StandingOrderUpdates => validate_delete_link_standing_order_updates(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
                     LinkTypes::OrderToProducerDeliveries => {
                         validate_delete_link_order_to_producer_deliveries(
+                            action_hash(&op).clone(),
                             action,
                             create_link.clone(),
                             base_address,
@@ -976,6 +1517,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     }
                     LinkTypes::ProducerToProducerDeliveries => {
                         validate_delete_link_producer_to_producer_deliveries(
+                            action_hash(&op).clone(),
                             action,
                             create_link.clone(),
                             base_address,
@@ -1000,6 +1542,22 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.target_address,
                         create_link.tag,
                     ),
+                    LinkTypes::OrdersByMonth => validate_delete_link_orders_by_month(
+                        action_hash(&op).clone(),
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::OrdersByStatus => validate_delete_link_orders_by_status(
+                        action_hash(&op).clone(),
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
                     LinkTypes::OrderToAvailableProducts => {
                         validate_delete_link_order_to_available_products(
                             action_hash(&op).clone(),
@@ -1020,6 +1578,66 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             create_link.tag,
                         )
                     }
+                    LinkTypes::OrderToHouseholdPayments => {
+                        validate_delete_link_order_to_household_payments(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::HouseholdToHouseholdPayments => {
+                        validate_delete_link_household_to_household_payments(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::HouseholdToStandingOrders => {
+                        validate_delete_link_household_to_standing_orders(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::OrderToHouseholdPickups => {
+                        validate_delete_link_order_to_household_pickups(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::HouseholdToHouseholdPickups => {
+                        validate_delete_link_household_to_household_pickups(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::ProductToAvailableProducts => {
+                        validate_delete_link_product_to_available_products(
+                            action_hash(&op).clone(),
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
                 }
             }
             OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
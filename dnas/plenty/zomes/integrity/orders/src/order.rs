@@ -30,29 +30,284 @@ pub struct Order {
     pub status: OrderStatus,
 }
 
+impl OrderStatus {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrderStatus::Preparing => "Preparing",
+            OrderStatus::Open { .. } => "Open",
+            OrderStatus::Closed { .. } => "Closed",
+            OrderStatus::Processed { .. } => "Processed",
+            OrderStatus::Finished { .. } => "Finished",
+        }
+    }
+}
+
+pub const ORDER_STATUS_NAMES: [&str; 5] = ["Preparing", "Open", "Closed", "Processed", "Finished"];
+
+/// Index of the orders with the given status, by their original order hash
+pub fn orders_by_status_path(status_name: &str) -> Path {
+    Path::from(format!("orders_by_status.{status_name}"))
+}
+
+/// Index of the orders created in the year and month (UTC) of the given timestamp
+pub fn orders_by_month_path(timestamp: Timestamp) -> Path {
+    let (year, month) = year_and_month(timestamp);
+    orders_by_year_and_month_path(year, month)
+}
+
+pub fn orders_by_year_and_month_path(year: i64, month: u32) -> Path {
+    Path::from(format!("orders_by_month.{year}.{month:02}"))
+}
+
+/// OrdersByMonth links carry the creation timestamp of the order, so that
+/// orders can be filtered within a month without fetching them
+pub fn orders_by_month_tag(created_at: Timestamp) -> LinkTag {
+    LinkTag::new(created_at.as_micros().to_be_bytes().to_vec())
+}
+
+pub fn order_created_at(tag: &LinkTag) -> Option<Timestamp> {
+    let bytes: [u8; 8] = tag.0.clone().try_into().ok()?;
+    Some(Timestamp::from_micros(i64::from_be_bytes(bytes)))
+}
+
+/// Converts a timestamp to its UTC year and month, without pulling a date library into the wasm
+pub fn year_and_month(timestamp: Timestamp) -> (i64, u32) {
+    let days = timestamp.as_micros().div_euclid(86_400_000_000);
+    // Civil from days algorithm, from http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32)
+}
+
 pub fn validate_create_order(
     action_hash: &ActionHash,
     action: EntryCreationAction,
-    _order: Order,
+    order: Order,
 ) -> ExternResult<ValidateCallbackResult> {
-    let validate = validate_agent_had_undeleted_role_claim_at_the_time(
-        action.author(),
-        action_hash,
-        &String::from(ORDER_MANAGER),
-        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
-    )?;
+    let validate = validate_was_order_manager(action.author(), action_hash)?;
     let ValidateCallbackResult::Valid = validate else {
         return Ok(validate);
     };
+    if let EntryCreationAction::Create(_) = action {
+        if order.status.ne(&OrderStatus::Preparing) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Orders must be created in the Preparing status",
+            )));
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_update_order(
-    _action: Update,
-    _order: Order,
+    action_hash: &ActionHash,
+    action: Update,
+    order: Order,
     _original_action: EntryCreationAction,
-    _original_order: Order,
+    original_order: Order,
 ) -> ExternResult<ValidateCallbackResult> {
+    let was_order_manager = validate_was_order_manager(&action.author, action_hash)?;
+    let ValidateCallbackResult::Valid = was_order_manager else {
+        return Ok(was_order_manager);
+    };
+
+    let order_hash = original_order_hash(&action)?;
+
+    validate_order_status_transition(&order_hash, &action, &original_order.status, &order.status)
+}
+
+/// Follows the chain of updates back to the action that created the Order
+pub fn original_order_hash(update: &Update) -> ExternResult<ActionHash> {
+    let mut order_hash = update.original_action_address.clone();
+    loop {
+        let previous_action = must_get_action(order_hash.clone())?;
+        match previous_action.action() {
+            Action::Update(previous_update) => {
+                order_hash = previous_update.original_action_address.clone();
+            }
+            _ => return Ok(order_hash),
+        }
+    }
+}
+
+/// An Order can only move forward through its lifecycle:
+///
+/// Preparing -> Open -> Closed -> Processed -> Finished
+///
+/// With these exceptions:
+/// - Preparing -> Preparing, to edit an order that hasn't been opened yet
+/// - Open -> Open, to extend the deadline or change the available products
+/// - Closed -> Open, to reopen an order before it gets processed
+///
+/// The deadline of an Open order can't be moved earlier, and the order can only be closed
+/// after its deadline, since household orders are validated against whichever Open revision
+/// their author cites
+pub fn validate_order_status_transition(
+    order_hash: &ActionHash,
+    action: &Update,
+    previous_status: &OrderStatus,
+    new_status: &OrderStatus,
+) -> ExternResult<ValidateCallbackResult> {
+    match (previous_status, new_status) {
+        (OrderStatus::Preparing, OrderStatus::Preparing) => Ok(ValidateCallbackResult::Valid),
+        (
+            OrderStatus::Open {
+                deadline: previous_deadline,
+                ..
+            },
+            OrderStatus::Open { deadline, .. },
+        ) if deadline.lt(previous_deadline) => Ok(ValidateCallbackResult::Invalid(String::from(
+            "The deadline of an Open order can't be moved earlier",
+        ))),
+        (
+            OrderStatus::Preparing | OrderStatus::Open { .. } | OrderStatus::Closed { .. },
+            OrderStatus::Open {
+                deadline,
+                available_products,
+            },
+        ) => validate_open_status(order_hash, action, deadline, available_products),
+        (OrderStatus::Open { deadline, .. }, OrderStatus::Closed { .. })
+            if action.timestamp.lt(deadline) =>
+        {
+            Ok(ValidateCallbackResult::Invalid(String::from(
+                "An Open order can only be closed after its deadline",
+            )))
+        }
+        (OrderStatus::Open { deadline, .. }, OrderStatus::Closed { household_orders }) => {
+            validate_closed_status(order_hash, action, deadline, household_orders)
+        }
+        (
+            OrderStatus::Closed { .. },
+            OrderStatus::Processed {
+                producers_deliveries,
+            },
+        ) => validate_processed_status(order_hash, producers_deliveries),
+        (
+            OrderStatus::Processed { .. },
+            OrderStatus::Finished {
+                household_payments,
+                producers_invoices,
+            },
+        ) => validate_finished_status(order_hash, household_payments, producers_invoices),
+        _ => Ok(ValidateCallbackResult::Invalid(format!(
+            "Can't change the status of an order from {} to {}",
+            previous_status.name(),
+            new_status.name()
+        ))),
+    }
+}
+
+fn validate_open_status(
+    order_hash: &ActionHash,
+    action: &Update,
+    deadline: &Timestamp,
+    available_products: &[ActionHash],
+) -> ExternResult<ValidateCallbackResult> {
+    if deadline.le(&action.timestamp) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The deadline of an Open order must be in the future",
+        )));
+    }
+    for available_products_hash in available_products {
+        let record = must_get_valid_record(available_products_hash.clone())?;
+        let available_products = crate::AvailableProducts::try_from(record)?;
+        if available_products.order_hash.ne(order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the available products of an Open order must belong to that order",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Links can't be read deterministically during validation, so this only checks that the
+/// snapshot is consistent with what could have been linked from the order when it was closed
+///
+/// Only the household orders committed before the deadline of the Open revision the order was
+/// closed from make it into the order, unless they are late corrections from an order manager
+fn validate_closed_status(
+    order_hash: &ActionHash,
+    action: &Update,
+    deadline: &Timestamp,
+    household_orders: &[ActionHash],
+) -> ExternResult<ValidateCallbackResult> {
+    let mut households: HashSet<ActionHash> = HashSet::new();
+    for household_order_hash in household_orders {
+        let record = must_get_valid_record(household_order_hash.clone())?;
+        if record.action().timestamp().ge(&action.timestamp) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the household orders of a Closed order must have been committed before closing it",
+            )));
+        }
+        if !crate::is_household_order_within_deadline(household_order_hash, deadline)? {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the household orders of a Closed order must have been committed before its deadline",
+            )));
+        }
+        let household_order = crate::HouseholdOrder::try_from(record)?;
+        if household_order.order_hash.ne(order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the household orders of a Closed order must belong to that order",
+            )));
+        }
+        if !households.insert(household_order.household_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "A Closed order can only contain one household order per household",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_processed_status(
+    order_hash: &ActionHash,
+    producers_deliveries: &[ActionHash],
+) -> ExternResult<ValidateCallbackResult> {
+    for producer_delivery_hash in producers_deliveries {
+        let record = must_get_valid_record(producer_delivery_hash.clone())?;
+        let producer_delivery = crate::ProducerDelivery::try_from(record)?;
+        if producer_delivery.order_hash.ne(order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the producer deliveries of a Processed order must belong to that order",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn validate_finished_status(
+    order_hash: &ActionHash,
+    household_payments: &[ActionHash],
+    producers_invoices: &[ActionHash],
+) -> ExternResult<ValidateCallbackResult> {
+    for household_payment_hash in household_payments {
+        let record = must_get_valid_record(household_payment_hash.clone())?;
+        let household_payment = crate::HouseholdPayment::try_from(record)?;
+        if household_payment.order_hash.ne(order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the household payments of a Finished order must belong to that order",
+            )));
+        }
+    }
+    for producer_invoice_hash in producers_invoices {
+        let record = must_get_valid_record(producer_invoice_hash.clone())?;
+        let producer_invoice = crate::ProducerInvoice::try_from(record)?;
+        if producer_invoice.order_hash.ne(order_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "All the producer invoices of a Finished order must belong to that order",
+            )));
+        }
+    }
     Ok(ValidateCallbackResult::Valid)
 }
 
@@ -62,12 +317,7 @@ pub fn validate_delete_order(
     _original_action: EntryCreationAction,
     _original_order: Order,
 ) -> ExternResult<ValidateCallbackResult> {
-    let validate = validate_agent_had_undeleted_role_claim_at_the_time(
-        &action.author,
-        action_hash,
-        &String::from(ORDER_MANAGER),
-        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
-    )?;
+    let validate = validate_was_order_manager(&action.author, action_hash)?;
     let ValidateCallbackResult::Valid = validate else {
         return Ok(validate);
     };
@@ -147,6 +397,105 @@ pub fn validate_create_link_all_orders(
     Ok(ValidateCallbackResult::Valid)
 }
 
+fn validate_was_order_manager(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_agent_had_undeleted_role_claim_at_the_time(
+        author,
+        action_hash,
+        &String::from(ORDER_MANAGER),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )
+}
+
+pub fn validate_create_link_orders_by_month(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let created_at = record.action().timestamp();
+    let _order = crate::Order::try_from(record)?;
+
+    if AnyLinkableHash::from(orders_by_month_path(created_at).path_entry_hash()?).ne(&base_address)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "OrdersByMonth links must have as the base the path for the month the order was created in",
+        )));
+    }
+    if orders_by_month_tag(created_at).ne(&tag) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "OrdersByMonth links must have as the tag the timestamp the order was created at",
+        )));
+    }
+
+    validate_was_order_manager(&action.author, &action_hash)
+}
+
+pub fn validate_delete_link_orders_by_month(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_was_order_manager(&action.author, &action_hash)
+}
+
+pub fn validate_create_link_orders_by_status(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let _order = crate::Order::try_from(record)?;
+
+    let mut is_status_path = false;
+    for status_name in ORDER_STATUS_NAMES {
+        if AnyLinkableHash::from(orders_by_status_path(status_name).path_entry_hash()?)
+            .eq(&base_address)
+        {
+            is_status_path = true;
+        }
+    }
+    if !is_status_path {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "OrdersByStatus links must have as the base the path for one of the order statuses",
+        )));
+    }
+
+    validate_was_order_manager(&action.author, &action_hash)
+}
+
+pub fn validate_delete_link_orders_by_status(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_was_order_manager(&action.author, &action_hash)
+}
+
 pub fn validate_delete_link_all_orders(
     _action: DeleteLink,
     _original_action: CreateLink,
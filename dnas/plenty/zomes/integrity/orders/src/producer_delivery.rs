@@ -2,6 +2,9 @@ use std::collections::BTreeMap;
 
 use hdi::prelude::*;
 use producers_types::*;
+use roles_types::validate_agent_had_undeleted_role_claim_at_the_time;
+
+use crate::roles::{ORDER_MANAGER, ROLES_INTEGRITY_ZOME_NAME};
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct FixedProductDeliveryForHouseholds {
@@ -43,11 +46,72 @@ pub enum ProductDelivery {
 pub struct ProducerDelivery {
     pub order_hash: ActionHash,
     pub producer_hash: ActionHash,
+    pub latest_producer_hash: ActionHash,
     pub products: BTreeMap<ActionHashB64, ProductDelivery>,
 }
 
+/// Only the liason of the producer or the order managers can record what a producer delivered
+///
+/// The liason is read from `latest_producer_hash`, which must be a revision of `producer_hash`,
+/// same as for AvailableProducts
+pub fn validate_can_change_producer_delivery(
+    agent: &AgentPubKey,
+    chain_top: &ActionHash,
+    producer_delivery: &ProducerDelivery,
+) -> ExternResult<ValidateCallbackResult> {
+    if !is_revision_of(
+        &producer_delivery.latest_producer_hash,
+        &producer_delivery.producer_hash,
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The latest_producer_hash for a ProducerDelivery must be a revision of its producer",
+        )));
+    }
+    let record = must_get_valid_record(producer_delivery.latest_producer_hash.clone())?;
+    let producer: Producer = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+
+    if producer.liason.ne(agent) {
+        let was_order_manager = validate_agent_had_undeleted_role_claim_at_the_time(
+            agent,
+            chain_top,
+            &String::from(ORDER_MANAGER),
+            &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+        )?;
+        let ValidateCallbackResult::Valid = was_order_manager else {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Only the liasons or the order managers can change ProducerDeliveries",
+            )));
+        };
+    }
+
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn producer_delivery_from_target(target: AnyLinkableHash) -> ExternResult<ProducerDelivery> {
+    let target_hash = target
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(target_hash)?;
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))
+}
+
 pub fn validate_create_producer_delivery(
-    _action: EntryCreationAction,
+    action_hash: ActionHash,
+    action: EntryCreationAction,
     producer_delivery: ProducerDelivery,
 ) -> ExternResult<ValidateCallbackResult> {
     let record = must_get_valid_record(producer_delivery.order_hash.clone())?;
@@ -66,11 +130,18 @@ pub fn validate_create_producer_delivery(
         .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
             "Dependant action must be accompanied by an entry"
         ))))?;
+
+    let can_change =
+        validate_can_change_producer_delivery(action.author(), &action_hash, &producer_delivery)?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_update_producer_delivery(
-    _action: Update,
+    action_hash: ActionHash,
+    action: Update,
     producer_delivery: ProducerDelivery,
     _original_action: EntryCreationAction,
     original_producer_delivery: ProducerDelivery,
@@ -92,30 +163,56 @@ pub fn validate_update_producer_delivery(
             "Can't change the producer_hash for a ProducerDelivery",
         )));
     }
+
+    // A replaced liason can't get back to the revision of the producer that named them
+    if !is_revision_of(
+        &producer_delivery.latest_producer_hash,
+        &original_producer_delivery.latest_producer_hash,
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The latest_producer_hash of a ProducerDelivery can't go back to an older revision of its producer",
+        )));
+    }
+
+    let can_change =
+        validate_can_change_producer_delivery(&action.author, &action_hash, &producer_delivery)?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_delete_producer_delivery(
-    _action: Delete,
+    action_hash: ActionHash,
+    action: Delete,
     _original_action: EntryCreationAction,
-    _original_producer_delivery: ProducerDelivery,
+    original_producer_delivery: ProducerDelivery,
 ) -> ExternResult<ValidateCallbackResult> {
+    let can_change = validate_can_change_producer_delivery(
+        &action.author,
+        &action_hash,
+        &original_producer_delivery,
+    )?;
+    let ValidateCallbackResult::Valid = can_change else {
+        return Ok(can_change);
+    };
     Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_create_link_order_to_producer_deliveries(
-    _action: CreateLink,
+    action_hash: ActionHash,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
     // Check the entry type for the given action hash
-    let action_hash = base_address
+    let base_hash = base_address
         .into_action_hash()
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "No action hash associated with link".to_string()
         )))?;
-    let record = must_get_valid_record(action_hash)?;
+    let record = must_get_valid_record(base_hash.clone())?;
     let _order: crate::Order = record
         .entry()
         .to_app_option()
@@ -123,49 +220,41 @@ pub fn validate_create_link_order_to_producer_deliveries(
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    // Check the entry type for the given action hash
-    let action_hash =
-        target_address
-            .into_action_hash()
-            .ok_or(wasm_error!(WasmErrorInner::Guest(
-                "No action hash associated with link".to_string()
-            )))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _producer_delivery: crate::ProducerDelivery = record
-        .entry()
-        .to_app_option()
-        .map_err(|e| wasm_error!(e))?
-        .ok_or(wasm_error!(WasmErrorInner::Guest(
-            "Linked action must reference an entry".to_string()
-        )))?;
-    // TODO: add the appropriate validation rules
-    Ok(ValidateCallbackResult::Valid)
+    let producer_delivery = producer_delivery_from_target(target_address)?;
+
+    if producer_delivery.order_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("OrderToProducerDeliveries links can only have as the base the order hash specified in the ProducerDelivery entry")));
+    }
+
+    validate_can_change_producer_delivery(&action.author, &action_hash, &producer_delivery)
 }
 
 pub fn validate_delete_link_order_to_producer_deliveries(
-    _action: DeleteLink,
+    action_hash: ActionHash,
+    action: DeleteLink,
     _original_action: CreateLink,
     _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
+    target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
-    Ok(ValidateCallbackResult::Valid)
+    let producer_delivery = producer_delivery_from_target(target)?;
+    validate_can_change_producer_delivery(&action.author, &action_hash, &producer_delivery)
 }
 
 pub fn validate_create_link_producer_to_producer_deliveries(
-    _action: CreateLink,
+    action_hash: ActionHash,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
     // Check the entry type for the given action hash
-    let action_hash = base_address
+    let base_hash = base_address
         .into_action_hash()
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "No action hash associated with link".to_string()
         )))?;
-    let record = must_get_valid_record(action_hash)?;
+    let record = must_get_valid_record(base_hash.clone())?;
     let _producer: Producer = record
         .entry()
         .to_app_option()
@@ -173,32 +262,23 @@ pub fn validate_create_link_producer_to_producer_deliveries(
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    // Check the entry type for the given action hash
-    let action_hash =
-        target_address
-            .into_action_hash()
-            .ok_or(wasm_error!(WasmErrorInner::Guest(
-                "No action hash associated with link".to_string()
-            )))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _producer_delivery: crate::ProducerDelivery = record
-        .entry()
-        .to_app_option()
-        .map_err(|e| wasm_error!(e))?
-        .ok_or(wasm_error!(WasmErrorInner::Guest(
-            "Linked action must reference an entry".to_string()
-        )))?;
-    // TODO: add the appropriate validation rules
-    Ok(ValidateCallbackResult::Valid)
+    let producer_delivery = producer_delivery_from_target(target_address)?;
+
+    if producer_delivery.producer_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("ProducerToProducerDeliveries links can only have as the base the producer hash specified in the ProducerDelivery entry")));
+    }
+
+    validate_can_change_producer_delivery(&action.author, &action_hash, &producer_delivery)
 }
 
 pub fn validate_delete_link_producer_to_producer_deliveries(
-    _action: DeleteLink,
+    action_hash: ActionHash,
+    action: DeleteLink,
     _original_action: CreateLink,
     _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
+    target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: add the appropriate validation rules
-    Ok(ValidateCallbackResult::Valid)
+    let producer_delivery = producer_delivery_from_target(target)?;
+    validate_can_change_producer_delivery(&action.author, &action_hash, &producer_delivery)
 }
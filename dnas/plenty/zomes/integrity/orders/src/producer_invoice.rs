@@ -1,15 +1,123 @@
 use hdi::prelude::*;
+use pricing::round_cents;
 use producers_types::*;
 use roles_types::*;
 
 use crate::roles::{BOOKKEEPER_ROLE, ROLES_INTEGRITY_ZOME_NAME};
 
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct InvoiceLine {
+    pub original_product_hash: ActionHash,
+    /// Number of packages billed: for estimated products it's the delivered weight or volume
+    /// divided by the amount per package, so it can be fractional
+    pub quantity: f32,
+    pub unit_price_cents: u32,
+    pub vat_percentage: f32,
+    pub subtotal_cents: u64,
+    pub vat_cents: u64,
+    pub total_cents: u64,
+}
+
+pub const MAX_INVOICE_LINE_QUANTITY: f32 = 1_000_000.0;
+pub const MAX_VAT_PERCENTAGE: f32 = 100.0;
+
+impl InvoiceLine {
+    /// Builds a line with its totals, rounding the subtotal and the VAT to the cent
+    ///
+    /// Returns None if the quantity is not positive, or the quantity or the VAT percentage
+    /// are out of range
+    pub fn new(
+        original_product_hash: ActionHash,
+        quantity: f32,
+        unit_price_cents: u32,
+        vat_percentage: f32,
+    ) -> Option<Self> {
+        if !quantity.is_finite() || quantity <= 0.0 || quantity > MAX_INVOICE_LINE_QUANTITY {
+            return None;
+        }
+        if !vat_percentage.is_finite()
+            || vat_percentage < 0.0
+            || vat_percentage > MAX_VAT_PERCENTAGE
+        {
+            return None;
+        }
+        let subtotal_cents = round_cents(quantity as f64 * unit_price_cents as f64);
+        let vat_cents = round_cents(subtotal_cents as f64 * vat_percentage as f64 / 100.0);
+        Some(InvoiceLine {
+            original_product_hash,
+            quantity,
+            unit_price_cents,
+            vat_percentage,
+            subtotal_cents,
+            vat_cents,
+            total_cents: subtotal_cents.checked_add(vat_cents)?,
+        })
+    }
+}
+
 #[derive(Clone, PartialEq)]
 #[hdk_entry_helper]
 pub struct ProducerInvoice {
     pub order_hash: ActionHash,
     pub producer_hash: ActionHash,
     pub invoice: EntryHash,
+    /// What the producer billed, line by line; empty if only the invoice file was uploaded
+    #[serde(default)]
+    pub lines: Vec<InvoiceLine>,
+}
+
+fn validate_invoice_lines(
+    producer_invoice: &ProducerInvoice,
+) -> ExternResult<ValidateCallbackResult> {
+    for line in &producer_invoice.lines {
+        let record = must_get_valid_record(line.original_product_hash.clone())?;
+        let product: Product = record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?
+            .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+                "Dependant action must be accompanied by an entry"
+            ))))?;
+
+        if product.producer_hash.ne(&producer_invoice.producer_hash) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "Invoice lines can only bill products from the producer of the invoice",
+            )));
+        }
+        if !line.quantity.is_finite()
+            || line.quantity <= 0.0
+            || line.quantity > MAX_INVOICE_LINE_QUANTITY
+        {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "The quantity of each invoice line must be greater than 0 and at most {MAX_INVOICE_LINE_QUANTITY}",
+            )));
+        }
+        if !line.vat_percentage.is_finite()
+            || line.vat_percentage < 0.0
+            || line.vat_percentage > MAX_VAT_PERCENTAGE
+        {
+            return Ok(ValidateCallbackResult::Invalid(format!(
+                "The VAT percentage of an invoice line must be between 0 and {MAX_VAT_PERCENTAGE}",
+            )));
+        }
+
+        let Some(expected) = InvoiceLine::new(
+            line.original_product_hash.clone(),
+            line.quantity,
+            line.unit_price_cents,
+            line.vat_percentage,
+        ) else {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The totals of an invoice line are too large",
+            )));
+        };
+        if expected.ne(line) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The totals of an invoice line don't match its quantity, unit price and VAT",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
 }
 
 pub fn validate_create_producer_invoice(
@@ -45,7 +153,7 @@ pub fn validate_create_producer_invoice(
         return Ok(was_bookkeeper);
     };
 
-    Ok(ValidateCallbackResult::Valid)
+    validate_invoice_lines(&producer_invoice)
 }
 
 pub fn validate_update_producer_invoice(
@@ -82,7 +190,8 @@ pub fn validate_update_producer_invoice(
     let ValidateCallbackResult::Valid = was_bookkeeper else {
         return Ok(was_bookkeeper);
     };
-    Ok(ValidateCallbackResult::Valid)
+
+    validate_invoice_lines(&producer_invoice)
 }
 
 pub fn validate_delete_producer_invoice(
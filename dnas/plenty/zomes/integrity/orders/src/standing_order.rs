@@ -0,0 +1,222 @@
+use std::collections::HashSet;
+
+use hdi::prelude::*;
+use households_types::*;
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct StandingProductOrder {
+    pub original_product_hash: ActionHash,
+    pub amount: u32,
+}
+
+/// The basket a household orders every time, applied to each order when it opens
+#[derive(Clone, PartialEq)]
+#[hdk_entry_helper]
+pub struct StandingOrder {
+    pub household_hash: ActionHash,
+    pub products: Vec<StandingProductOrder>,
+}
+
+fn validate_standing_product_orders(
+    standing_order: &StandingOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    let mut products: HashSet<ActionHash> = HashSet::new();
+    for product_order in &standing_order.products {
+        if product_order.amount == 0 {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "The amount of each product in a StandingOrder must be greater than 0",
+            )));
+        }
+        if !products.insert(product_order.original_product_hash.clone()) {
+            return Ok(ValidateCallbackResult::Invalid(String::from(
+                "A product can only appear once in each StandingOrder",
+            )));
+        }
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_create_standing_order(
+    action_hash: ActionHash,
+    action: EntryCreationAction,
+    standing_order: StandingOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(standing_order.household_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+
+    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+        action.author().clone(),
+        action_hash,
+        standing_order.household_hash.clone(),
+    )?;
+    let ValidateCallbackResult::Valid = member_of_household else {
+        return Ok(member_of_household);
+    };
+
+    validate_standing_product_orders(&standing_order)
+}
+
+pub fn validate_update_standing_order(
+    action_hash: ActionHash,
+    action: Update,
+    standing_order: StandingOrder,
+    _original_action: EntryCreationAction,
+    original_standing_order: StandingOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    if standing_order
+        .household_hash
+        .ne(&original_standing_order.household_hash)
+    {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the household_hash for a StandingOrder",
+        )));
+    }
+
+    let member_of_household = validate_agent_was_member_of_household_at_the_time(
+        action.author,
+        action_hash,
+        standing_order.household_hash.clone(),
+    )?;
+    let ValidateCallbackResult::Valid = member_of_household else {
+        return Ok(member_of_household);
+    };
+
+    validate_standing_product_orders(&standing_order)
+}
+
+pub fn validate_delete_standing_order(
+    action_hash: ActionHash,
+    action: Delete,
+    _original_action: EntryCreationAction,
+    original_standing_order: StandingOrder,
+) -> ExternResult<ValidateCallbackResult> {
+    validate_agent_was_member_of_household_at_the_time(
+        action.author,
+        action_hash,
+        original_standing_order.household_hash,
+    )
+}
+
+pub fn validate_create_link_household_to_standing_orders(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash.clone())?;
+    let _household: Household = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let standing_order: crate::StandingOrder = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+
+    if standing_order.household_hash.ne(&base_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("HouseholdToStandingOrders links can only have as the base the household hash specified in the StandingOrder entry")));
+    }
+
+    validate_agent_was_member_of_household_at_the_time(
+        action.author,
+        action_hash,
+        standing_order.household_hash,
+    )
+}
+
+pub fn validate_delete_link_household_to_standing_orders(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let household_hash = base
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    validate_agent_was_member_of_household_at_the_time(action.author, action_hash, household_hash)
+}
+
+pub fn validate_create_link_standing_order_updates(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    // Check the entry type for the given action hash
+    let base_hash = base_address
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(base_hash)?;
+    let _standing_order: crate::StandingOrder = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    // Check the entry type for the given action hash
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let standing_order: crate::StandingOrder = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+
+    validate_agent_was_member_of_household_at_the_time(
+        action.author,
+        action_hash,
+        standing_order.household_hash,
+    )
+}
+
+pub fn validate_delete_link_standing_order_updates(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from(
+        "StandingOrderUpdates links cannot be deleted",
+    )))
+}
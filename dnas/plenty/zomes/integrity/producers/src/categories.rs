@@ -1,4 +1,8 @@
 use hdi::prelude::*;
+use roles_types::validate_agent_had_undeleted_role_claim_at_the_time;
+
+use crate::producer::validate_can_edit_product;
+use crate::roles::{ORDER_MANAGER, ROLES_INTEGRITY_ZOME_NAME};
 
 pub fn validate_create_link_categories_path(
     _action: CreateLink,
@@ -8,22 +12,38 @@ pub fn validate_create_link_categories_path(
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
+/// Removing a category from the categories tree is what renaming and merging categories do,
+/// so only the order managers can do it
+pub fn validate_can_change_categories(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    let was_order_manager = validate_agent_had_undeleted_role_claim_at_the_time(
+        author,
+        action_hash,
+        &String::from(ORDER_MANAGER),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+    let ValidateCallbackResult::Valid = was_order_manager else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the order managers can rename or merge categories",
+        )));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
 pub fn validate_delete_link_categories_path(
-    _action: DeleteLink,
+    action_hash: ActionHash,
+    action: DeleteLink,
     _original_action: CreateLink,
     _base: AnyLinkableHash,
     _target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    validate_can_change_categories(&action.author, &action_hash)
 }
 
-pub fn validate_create_link_category_to_product(
-    _action: CreateLink,
-    _base_address: AnyLinkableHash,
-    target_address: AnyLinkableHash,
-    _tag: LinkTag,
-) -> ExternResult<ValidateCallbackResult> {
+fn product_from_target(target_address: AnyLinkableHash) -> ExternResult<crate::Product> {
     let action_hash =
         target_address
             .into_action_hash()
@@ -31,21 +51,34 @@ pub fn validate_create_link_category_to_product(
                 "No action hash associated with link".to_string()
             )))?;
     let record = must_get_valid_record(action_hash)?;
-    let _product: crate::Product = record
+    let product: crate::Product = record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    Ok(ValidateCallbackResult::Valid)
+    Ok(product)
+}
+
+pub fn validate_create_link_category_to_product(
+    action_hash: ActionHash,
+    action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let product = product_from_target(target_address)?;
+    validate_can_edit_product(&action.author, &action_hash, &product)
 }
 pub fn validate_delete_link_category_to_product(
-    _action: DeleteLink,
+    action_hash: ActionHash,
+    action: DeleteLink,
     _original_action: CreateLink,
     _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
+    target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let product = product_from_target(target)?;
+    validate_can_edit_product(&action.author, &action_hash, &product)
 }
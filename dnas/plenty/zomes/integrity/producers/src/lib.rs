@@ -6,6 +6,9 @@ pub mod producer;
 pub use producer::*;
 pub mod categories;
 pub use categories::*;
+pub mod product_names;
+pub use product_names::*;
+pub mod roles;
 
 #[derive(Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -25,6 +28,7 @@ pub enum LinkTypes {
     AllProducers,
     ProducerToProducts,
     ProductUpdates,
+    ProductNamesIndex,
 }
 #[hdk_extern]
 pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
@@ -36,6 +40,19 @@ pub fn validate_agent_joining(
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
+
+pub fn action_hash(op: &Op) -> &ActionHash {
+    match op {
+        Op::StoreRecord(StoreRecord { record }) => record.action_address(),
+        Op::StoreEntry(StoreEntry { action, .. }) => &action.hashed.hash,
+        Op::RegisterUpdate(RegisterUpdate { update, .. }) => &update.hashed.hash,
+        Op::RegisterDelete(RegisterDelete { delete, .. }) => &delete.hashed.hash,
+        Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => &action.hashed.hash,
+        Op::RegisterCreateLink(RegisterCreateLink { create_link }) => &create_link.hashed.hash,
+        Op::RegisterDeleteLink(RegisterDeleteLink { delete_link, .. }) => &delete_link.hashed.hash,
+    }
+}
+
 #[hdk_extern]
 pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
     match op.flattened::<EntryTypes, LinkTypes>()? {
@@ -44,9 +61,11 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Producer(producer) => {
                     validate_create_producer(EntryCreationAction::Create(action), producer)
                 }
-                EntryTypes::Product(product) => {
-                    validate_create_product(EntryCreationAction::Create(action), product)
-                }
+                EntryTypes::Product(product) => validate_create_product(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    product,
+                ),
             },
             OpEntry::UpdateEntry {
                 app_entry, action, ..
@@ -54,9 +73,11 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Producer(producer) => {
                     validate_create_producer(EntryCreationAction::Update(action), producer)
                 }
-                EntryTypes::Product(product) => {
-                    validate_create_product(EntryCreationAction::Update(action), product)
-                }
+                EntryTypes::Product(product) => validate_create_product(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Update(action),
+                    product,
+                ),
             },
             _ => Ok(ValidateCallbackResult::Valid),
         },
@@ -86,6 +107,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             }
                         };
                         validate_update_product(
+                            action_hash(&op).clone(),
                             action,
                             product,
                             original_create_action,
@@ -104,6 +126,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             }
                         };
                         validate_update_producer(
+                            action_hash(&op).clone(),
                             action,
                             producer,
                             original_create_action,
@@ -155,11 +178,13 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             };
             match original_app_entry {
                 EntryTypes::Product(original_product) => validate_delete_product(
+                    action_hash(&op).clone(),
                     delete_entry.clone().action,
                     original_action,
                     original_product,
                 ),
                 EntryTypes::Producer(original_producer) => validate_delete_producer(
+                    action_hash(&op).clone(),
                     delete_entry.clone().action,
                     original_action,
                     original_producer,
@@ -179,21 +204,36 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
             LinkTypes::CategoriesPath => {
                 validate_create_link_categories_path(action, base_address, target_address, tag)
             }
-            LinkTypes::CategoryToProduct => {
-                validate_create_link_category_to_product(action, base_address, target_address, tag)
-            }
+            LinkTypes::CategoryToProduct => validate_create_link_category_to_product(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::ProducerUpdates => {
                 validate_create_link_producer_updates(action, base_address, target_address, tag)
             }
             LinkTypes::AllProducers => {
                 validate_create_link_all_producers(action, base_address, target_address, tag)
             }
-            LinkTypes::ProducerToProducts => {
-                validate_create_link_producer_to_products(action, base_address, target_address, tag)
-            }
+            LinkTypes::ProducerToProducts => validate_create_link_producer_to_products(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
             LinkTypes::ProductUpdates => {
                 validate_create_link_product_updates(action, base_address, target_address, tag)
             }
+            LinkTypes::ProductNamesIndex => validate_create_link_product_names_index(
+                action_hash(&op).clone(),
+                action,
+                base_address,
+                target_address,
+                tag,
+            ),
         },
         FlatOp::RegisterDeleteLink {
             link_type,
@@ -211,6 +251,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 tag,
             ),
             LinkTypes::CategoriesPath => validate_delete_link_categories_path(
+                action_hash(&op).clone(),
                 action,
                 original_action,
                 base_address,
@@ -218,6 +259,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 tag,
             ),
             LinkTypes::CategoryToProduct => validate_delete_link_category_to_product(
+                action_hash(&op).clone(),
                 action,
                 original_action,
                 base_address,
@@ -239,6 +281,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 tag,
             ),
             LinkTypes::ProducerToProducts => validate_delete_link_producer_to_products(
+                action_hash(&op).clone(),
                 action,
                 original_action,
                 base_address,
@@ -252,15 +295,25 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::ProductNamesIndex => validate_delete_link_product_names_index(
+                action_hash(&op).clone(),
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
         },
         FlatOp::StoreRecord(store_record) => match store_record {
             OpRecord::CreateEntry { app_entry, action } => match app_entry {
                 EntryTypes::Producer(producer) => {
                     validate_create_producer(EntryCreationAction::Create(action), producer)
                 }
-                EntryTypes::Product(product) => {
-                    validate_create_product(EntryCreationAction::Create(action), product)
-                }
+                EntryTypes::Product(product) => validate_create_product(
+                    action_hash(&op).clone(),
+                    EntryCreationAction::Create(action),
+                    product,
+                ),
             },
             OpRecord::UpdateEntry {
                 original_action_hash,
@@ -303,6 +356,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 }
                             };
                             validate_update_producer(
+                                action_hash(&op).clone(),
                                 action,
                                 producer,
                                 original_action,
@@ -314,6 +368,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     }
                     EntryTypes::Product(product) => {
                         let result = validate_create_product(
+                            action_hash(&op).clone(),
                             EntryCreationAction::Update(action.clone()),
                             product.clone(),
                         )?;
@@ -334,6 +389,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                                 }
                             };
                             validate_update_product(
+                                action_hash(&op).clone(),
                                 action,
                                 product,
                                 original_action,
@@ -392,12 +448,18 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     }
                 };
                 match original_app_entry {
-                    EntryTypes::Producer(original_producer) => {
-                        validate_delete_producer(action, original_action, original_producer)
-                    }
-                    EntryTypes::Product(original_product) => {
-                        validate_delete_product(action, original_action, original_product)
-                    }
+                    EntryTypes::Producer(original_producer) => validate_delete_producer(
+                        action_hash(&op).clone(),
+                        action,
+                        original_action,
+                        original_producer,
+                    ),
+                    EntryTypes::Product(original_product) => validate_delete_product(
+                        action_hash(&op).clone(),
+                        action,
+                        original_action,
+                        original_product,
+                    ),
                 }
             }
             OpRecord::CreateLink {
@@ -417,6 +479,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     validate_create_link_categories_path(action, base_address, target_address, tag)
                 }
                 LinkTypes::CategoryToProduct => validate_create_link_category_to_product(
+                    action_hash(&op).clone(),
                     action,
                     base_address,
                     target_address,
@@ -429,6 +492,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     validate_create_link_all_producers(action, base_address, target_address, tag)
                 }
                 LinkTypes::ProducerToProducts => validate_create_link_producer_to_products(
+                    action_hash(&op).clone(),
                     action,
                     base_address,
                     target_address,
@@ -437,6 +501,13 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 LinkTypes::ProductUpdates => {
                     validate_create_link_product_updates(action, base_address, target_address, tag)
                 }
+                LinkTypes::ProductNamesIndex => validate_create_link_product_names_index(
+                    action_hash(&op).clone(),
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
             },
             OpRecord::DeleteLink {
                 original_action_hash,
@@ -468,6 +539,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.tag,
                     ),
                     LinkTypes::CategoriesPath => validate_delete_link_categories_path(
+                        action_hash(&op).clone(),
                         action,
                         create_link.clone(),
                         base_address,
@@ -475,6 +547,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.tag,
                     ),
                     LinkTypes::CategoryToProduct => validate_delete_link_category_to_product(
+                        action_hash(&op).clone(),
                         action,
                         create_link.clone(),
                         base_address,
@@ -496,6 +569,7 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.tag,
                     ),
                     LinkTypes::ProducerToProducts => validate_delete_link_producer_to_products(
+                        action_hash(&op).clone(),
                         action,
                         create_link.clone(),
                         base_address,
@@ -509,6 +583,14 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         create_link.target_address,
                         create_link.tag,
                     ),
+                    LinkTypes::ProductNamesIndex => validate_delete_link_product_names_index(
+                        action_hash(&op).clone(),
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
                 }
             }
             OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
@@ -1,5 +1,74 @@
 use hdi::prelude::*;
 pub use producers_types::*;
+use roles_types::validate_agent_had_undeleted_role_claim_at_the_time;
+
+use crate::roles::{ORDER_MANAGER, ROLES_INTEGRITY_ZOME_NAME};
+
+/// Only the agents that the producer's editors setting permits, and the order managers,
+/// can change the producer and its products
+pub fn validate_can_edit_producer(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+    producer: &Producer,
+) -> ExternResult<ValidateCallbackResult> {
+    let is_editor = match &producer.editors {
+        ProducerEditors::Liason => producer.liason.eq(author),
+        ProducerEditors::AllMembers => true,
+        ProducerEditors::Members(members) => producer.liason.eq(author) || members.contains(author),
+    };
+    if is_editor {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+
+    let was_order_manager = validate_agent_had_undeleted_role_claim_at_the_time(
+        author,
+        action_hash,
+        &String::from(ORDER_MANAGER),
+        &ZomeName::from(ROLES_INTEGRITY_ZOME_NAME),
+    )?;
+    let ValidateCallbackResult::Valid = was_order_manager else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Only the editors of the producer and the order managers can change it or its products",
+        )));
+    };
+    Ok(ValidateCallbackResult::Valid)
+}
+
+pub fn validate_can_edit_producer_hash(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+    producer_hash: ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(producer_hash)?;
+    let producer: crate::Producer = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
+            "Dependant action must be accompanied by an entry"
+        ))))?;
+    validate_can_edit_producer(author, action_hash, &producer)
+}
+
+/// Integrity zomes can't look up the latest revision of the producer, so products point to
+/// the revision whose editors can change them, which the coordinator sets to the latest one
+///
+/// Known limitation: this can't be enforced here. An agent removed from the editors in a later
+/// revision of the producer can still create products pointing to a revision where they were an
+/// editor, and update or delete the products that still point to one. Only moving
+/// `latest_producer_hash` back to an older revision is rejected, in `validate_update_product`
+pub fn validate_can_edit_product(
+    author: &AgentPubKey,
+    action_hash: &ActionHash,
+    product: &Product,
+) -> ExternResult<ValidateCallbackResult> {
+    if !is_revision_of(&product.latest_producer_hash, &product.producer_hash)? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "The latest_producer_hash of a Product must be a revision of its producer",
+        )));
+    }
+    validate_can_edit_producer_hash(author, action_hash, product.latest_producer_hash.clone())
+}
 
 pub fn validate_create_producer(
     _action: EntryCreationAction,
@@ -8,19 +77,26 @@ pub fn validate_create_producer(
     Ok(ValidateCallbackResult::Valid)
 }
 pub fn validate_update_producer(
-    _action: Update,
+    action_hash: ActionHash,
+    action: Update,
     _producer: Producer,
     _original_action: EntryCreationAction,
-    _original_producer: Producer,
+    original_producer: Producer,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    // The editors of the revision being updated decide who can update it. Integrity zomes
+    // can't see whether that revision was already updated, so an agent removed from the
+    // editors in a later revision can still validly update an older one. This is a known
+    // limitation: the coordinator only updates the latest revision, and get_latest_producer
+    // follows the earliest update of each revision, so those late branches are ignored
+    validate_can_edit_producer(&action.author, &action_hash, &original_producer)
 }
 pub fn validate_delete_producer(
-    _action: Delete,
+    action_hash: ActionHash,
+    action: Delete,
     _original_action: EntryCreationAction,
-    _original_producer: Producer,
+    original_producer: Producer,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    validate_can_edit_producer(&action.author, &action_hash, &original_producer)
 }
 pub fn validate_create_link_liason_to_producers(
     _action: CreateLink,
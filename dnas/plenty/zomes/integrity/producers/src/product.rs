@@ -1,82 +1,61 @@
 use hdi::prelude::*;
+use producers_types::*;
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub enum PackagingUnit {
-    Piece,
-    Kilograms,
-    Grams,
-    Liters,
-    Milliliters,
-    Pounds,
-    Ounces,
-}
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
-pub struct Packaging {
-    pub unit: PackagingUnit,
-    pub number_of_packages: u32,
-    pub amount_per_package: f32,
-    pub estimate: bool,
-}
-#[hdk_entry_helper]
-#[derive(Clone, PartialEq)]
-pub struct Product {
-    pub producer_hash: ActionHash,
-    pub name: String,
-    pub product_id: String,
-    pub description: String,
-    pub categories: Vec<String>,
-    pub packaging: Packaging,
-    pub maximum_available: Option<u32>,
-    pub price_cents: u32,
-    pub vat_percentage: f32,
-    pub margin_percentage: Option<f32>,
-    pub origin: Option<String>,
-    pub ingredients: Option<String>,
-}
+use crate::producer::validate_can_edit_product;
 
 pub fn validate_create_product(
-    _action: EntryCreationAction,
+    action_hash: ActionHash,
+    action: EntryCreationAction,
     product: Product,
 ) -> ExternResult<ValidateCallbackResult> {
-    let record = must_get_valid_record(product.producer_hash.clone())?;
-    let _producer: crate::Producer = record
-        .entry()
-        .to_app_option()
-        .map_err(|e| wasm_error!(e))?
-        .ok_or(wasm_error!(WasmErrorInner::Guest(String::from(
-            "Dependant action must be accompanied by an entry"
-        ))))?;
-    Ok(ValidateCallbackResult::Valid)
+    validate_can_edit_product(action.author(), &action_hash, &product)
 }
 
 pub fn validate_update_product(
-    _action: Update,
-    _product: Product,
+    action_hash: ActionHash,
+    action: Update,
+    product: Product,
     _original_action: EntryCreationAction,
-    _original_product: Product,
+    original_product: Product,
 ) -> ExternResult<ValidateCallbackResult> {
-    // TODO: check all possible attacks
-    Ok(ValidateCallbackResult::Valid)
+    if product.producer_hash.ne(&original_product.producer_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't change the producer_hash for a Product",
+        )));
+    }
+    if !is_revision_of(
+        &product.latest_producer_hash,
+        &original_product.latest_producer_hash,
+    )? {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "Can't point a Product to an older revision of its producer",
+        )));
+    }
+    validate_can_edit_product(&action.author, &action_hash, &product)
 }
 pub fn validate_delete_product(
-    _action: Delete,
+    action_hash: ActionHash,
+    action: Delete,
     _original_action: EntryCreationAction,
-    _original_product: Product,
+    original_product: Product,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    // Deletes carry no entry, so the producer revision of the deleted product decides
+    validate_can_edit_product(&action.author, &action_hash, &original_product)
 }
 pub fn validate_create_link_producer_to_products(
-    _action: CreateLink,
+    action_hash: ActionHash,
+    action: CreateLink,
     base_address: AnyLinkableHash,
     target_address: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    let action_hash = base_address
-        .into_action_hash()
-        .ok_or(wasm_error!(WasmErrorInner::Guest(
-            "No action hash associated with link".to_string()
-        )))?;
-    let record = must_get_valid_record(action_hash)?;
+    let producer_hash =
+        base_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(producer_hash.clone())?;
     let _producer: crate::Producer = record
         .entry()
         .to_app_option()
@@ -84,30 +63,49 @@ pub fn validate_create_link_producer_to_products(
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    let action_hash =
+    let product_hash =
         target_address
             .into_action_hash()
             .ok_or(wasm_error!(WasmErrorInner::Guest(
                 "No action hash associated with link".to_string()
             )))?;
-    let record = must_get_valid_record(action_hash)?;
-    let _product: crate::Product = record
+    let record = must_get_valid_record(product_hash)?;
+    let product: crate::Product = record
         .entry()
         .to_app_option()
         .map_err(|e| wasm_error!(e))?
         .ok_or(wasm_error!(WasmErrorInner::Guest(
             "Linked action must reference an entry".to_string()
         )))?;
-    Ok(ValidateCallbackResult::Valid)
+
+    if product.producer_hash.ne(&producer_hash) {
+        return Ok(ValidateCallbackResult::Invalid(String::from("ProducerToProducts links can only have as the base the producer hash specified in the Product entry")));
+    }
+
+    validate_can_edit_product(&action.author, &action_hash, &product)
 }
 pub fn validate_delete_link_producer_to_products(
-    _action: DeleteLink,
+    action_hash: ActionHash,
+    action: DeleteLink,
     _original_action: CreateLink,
     _base: AnyLinkableHash,
-    _target: AnyLinkableHash,
+    target: AnyLinkableHash,
     _tag: LinkTag,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let product_hash = target
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(product_hash)?;
+    let product: crate::Product = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    validate_can_edit_product(&action.author, &action_hash, &product)
 }
 pub fn validate_create_link_product_updates(
     _action: CreateLink,
@@ -0,0 +1,122 @@
+use hdi::prelude::*;
+
+use crate::producer::validate_can_edit_product;
+
+/// Longest prefix of a name token that gets its own index path
+pub const PRODUCT_NAME_PREFIX_LENGTH: usize = 3;
+
+/// Lowercase words of the product name, e.g. "Organic Rice (1kg)" gives ["organic", "rice", "1kg"]
+pub fn product_name_tokens(name: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    for token in name
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(|token| token.to_lowercase())
+    {
+        if !tokens.contains(&token) {
+            tokens.push(token);
+        }
+    }
+    tokens
+}
+
+/// Prefixes of the token under which it is indexed, e.g. "rice" gives ["r", "ri", "ric"]
+pub fn product_name_token_prefixes(token: &str) -> Vec<String> {
+    let chars: Vec<char> = token.chars().collect();
+    (1..=chars.len().min(PRODUCT_NAME_PREFIX_LENGTH))
+        .map(|length| chars[..length].iter().collect())
+        .collect()
+}
+
+/// The prefix under which to search for the tokens starting with the given query token
+pub fn product_name_query_prefix(query_token: &str) -> String {
+    query_token
+        .chars()
+        .take(PRODUCT_NAME_PREFIX_LENGTH)
+        .collect()
+}
+
+pub fn product_names_path(prefix: &str) -> Path {
+    Path::from(format!("product_names.{prefix}"))
+}
+
+/// ProductNamesIndex links carry the whole token, so that search results
+/// can be filtered without fetching the products
+pub fn product_name_tag(token: &str) -> LinkTag {
+    LinkTag::new(token.as_bytes().to_vec())
+}
+
+pub fn product_name_token(tag: &LinkTag) -> Option<String> {
+    String::from_utf8(tag.0.clone()).ok()
+}
+
+pub fn validate_create_link_product_names_index(
+    action_hash: ActionHash,
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let target_hash =
+        target_address
+            .into_action_hash()
+            .ok_or(wasm_error!(WasmErrorInner::Guest(
+                "No action hash associated with link".to_string()
+            )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let product: crate::Product = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+
+    let Some(token) = product_name_token(&tag) else {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "ProductNamesIndex links must have the name token as their tag",
+        )));
+    };
+    if product_name_tokens(&token) != vec![token.clone()] {
+        return Ok(ValidateCallbackResult::Invalid(format!(
+            "\"{token}\" is not a normalized product name token"
+        )));
+    }
+    let mut is_prefix_path = false;
+    for prefix in product_name_token_prefixes(&token) {
+        if AnyLinkableHash::from(product_names_path(&prefix).path_entry_hash()?).eq(&base_address) {
+            is_prefix_path = true;
+        }
+    }
+    if !is_prefix_path {
+        return Ok(ValidateCallbackResult::Invalid(String::from(
+            "ProductNamesIndex links must have as the base the path for one of the prefixes of their token",
+        )));
+    }
+
+    validate_can_edit_product(&action.author, &action_hash, &product)
+}
+
+pub fn validate_delete_link_product_names_index(
+    action_hash: ActionHash,
+    action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let target_hash = target
+        .into_action_hash()
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "No action hash associated with link".to_string()
+        )))?;
+    let record = must_get_valid_record(target_hash)?;
+    let product: crate::Product = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(wasm_error!(WasmErrorInner::Guest(
+            "Linked action must reference an entry".to_string()
+        )))?;
+    validate_can_edit_product(&action.author, &action_hash, &product)
+}
@@ -0,0 +1,2 @@
+pub const ORDER_MANAGER: &str = "order_manager";
+pub const ROLES_INTEGRITY_ZOME_NAME: &str = "roles_integrity";
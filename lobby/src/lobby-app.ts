@@ -16,6 +16,17 @@ import { core } from "@tauri-apps/api";
 import { SlInput } from "@shoelace-style/shoelace";
 import { onOpenUrl } from "@tauri-apps/plugin-deep-link";
 
+// Same maximum as the club settings in the DNA properties
+const MAX_DECIMAL_PLACES = 4;
+
+function isValidDecimalPlaces(decimalPlaces: number) {
+  return (
+    Number.isInteger(decimalPlaces) &&
+    decimalPlaces >= 0 &&
+    decimalPlaces <= MAX_DECIMAL_PLACES
+  );
+}
+
 @localized()
 @customElement("lobby-app")
 export class LobbyApp extends SignalWatcher(LitElement) {
@@ -24,6 +35,9 @@ export class LobbyApp extends SignalWatcher(LitElement) {
 
   @state() _inviteLink: string | undefined;
 
+  @state() _currencyCode = "EUR";
+  @state() _decimalPlaces = 2;
+
   async firstUpdated() {
     await onOpenUrl((urls) => {
       if (urls[0].startsWith("plenty://")) {
@@ -34,10 +48,27 @@ export class LobbyApp extends SignalWatcher(LitElement) {
   }
 
   async joinPlentyInstance(url: string) {
-    const progenitor = url.split("plenty://")[1]!;
+    const [progenitor, query] = url.split("plenty://")[1]!.split("?");
+    const params = new URLSearchParams(query);
+    const currency = params.get("currency");
+    const decimals = params.get("decimals");
+    const decimalPlaces = decimals ? parseInt(decimals) : undefined;
+    if (decimalPlaces !== undefined && !isValidDecimalPlaces(decimalPlaces)) {
+      notifyError(msg("Invalid invite link."));
+      return;
+    }
+    // Old invite links don't carry the club settings
+    const clubSettings =
+      currency && decimalPlaces !== undefined
+        ? {
+            currency_code: currency,
+            decimal_places: decimalPlaces,
+          }
+        : undefined;
     try {
       await core.invoke("join_plenty_instance", {
         progenitor,
+        clubSettings,
       });
     } catch (e) {
       console.error(e);
@@ -125,14 +156,47 @@ export class LobbyApp extends SignalWatcher(LitElement) {
                     "Otherwise, create a new buyers club by clicking the button below.",
                   )}</span
                 >
+                <div class="row" style="gap: 12px">
+                  <sl-input
+                    style="flex: 1"
+                    .label=${msg("Currency")}
+                    .value=${this._currencyCode}
+                    maxlength="3"
+                    @input=${(e: InputEvent) => {
+                      this._currencyCode = (e.target as SlInput).value
+                        .trim()
+                        .toUpperCase();
+                    }}
+                  ></sl-input>
+                  <sl-input
+                    style="flex: 1"
+                    type="number"
+                    min="0"
+                    max=${MAX_DECIMAL_PLACES}
+                    .label=${msg("Decimal places")}
+                    .value=${`${this._decimalPlaces}`}
+                    @input=${(e: InputEvent) => {
+                      this._decimalPlaces = parseInt(
+                        (e.target as SlInput).value,
+                      );
+                    }}
+                  ></sl-input>
+                </div>
                 <sl-button
                   variant="primary"
                   .loading=${this._creating}
+                  .disabled=${!this._currencyCode ||
+                  !isValidDecimalPlaces(this._decimalPlaces)}
                   @click=${async () => {
                     if (this._creating) return;
                     this._creating = true;
                     try {
-                      await core.invoke("create_plenty_instance");
+                      await core.invoke("create_plenty_instance", {
+                        clubSettings: {
+                          currency_code: this._currencyCode,
+                          decimal_places: this._decimalPlaces,
+                        },
+                      });
                     } catch (e) {
                       console.error(e);
                       notifyError(msg("Error creating the buyers club."));
@@ -1,6 +1,7 @@
 use std::collections::HashMap;
 
 use anyhow::anyhow;
+use club_types::ClubSettings;
 use holochain_client::AgentPubKey;
 use holochain_types::{
     app::{AppBundle, AppManifest},
@@ -9,6 +10,7 @@ use holochain_types::{
     web_app::{WebAppBundle, WebAppManifest},
 };
 use mr_bundle::Bundle;
+use serde::Serialize;
 use tauri::{AppHandle, WebviewWindow};
 use tauri_plugin_holochain::HolochainExt;
 use tempdir::TempDir;
@@ -21,8 +23,12 @@ pub fn plenty_happ_bundle() -> WebAppBundle {
 }
 
 #[tauri::command]
-pub async fn create_plenty_instance(app: AppHandle, window: WebviewWindow) -> Result<(), String> {
-    internal_create_plenty_instance(app, window)
+pub async fn create_plenty_instance(
+    app: AppHandle,
+    window: WebviewWindow,
+    club_settings: Option<ClubSettings>,
+) -> Result<(), String> {
+    internal_create_plenty_instance(app, window, club_settings)
         .await
         .map_err(|err| format!("{:?}", err))
 }
@@ -30,6 +36,7 @@ pub async fn create_plenty_instance(app: AppHandle, window: WebviewWindow) -> Re
 async fn internal_create_plenty_instance(
     app: AppHandle,
     window: WebviewWindow,
+    club_settings: Option<ClubSettings>,
 ) -> anyhow::Result<()> {
     let admin_ws = app.holochain()?.admin_websocket().await?;
 
@@ -38,7 +45,9 @@ async fn internal_create_plenty_instance(
         .await
         .map_err(|err| anyhow!("{:?}", err))?;
 
-    let bundle = override_progenitor_in_web_happ(plenty_happ_bundle(), agent_key.clone()).await?;
+    let bundle =
+        override_properties_in_web_happ(plenty_happ_bundle(), agent_key.clone(), club_settings)
+            .await?;
 
     app.holochain()?
         .install_web_app(
@@ -69,8 +78,9 @@ pub async fn join_plenty_instance(
     app: AppHandle,
     window: WebviewWindow,
     progenitor: AgentPubKeyB64,
+    club_settings: Option<ClubSettings>,
 ) -> Result<(), String> {
-    internal_join_plenty_instance(app, window, progenitor.into())
+    internal_join_plenty_instance(app, window, progenitor.into(), club_settings)
         .await
         .map_err(|err| format!("{:?}", err))
 }
@@ -79,8 +89,11 @@ async fn internal_join_plenty_instance(
     app: AppHandle,
     window: WebviewWindow,
     progenitor: AgentPubKey,
+    club_settings: Option<ClubSettings>,
 ) -> anyhow::Result<()> {
-    let bundle = override_progenitor_in_web_happ(plenty_happ_bundle(), progenitor.clone()).await?;
+    let bundle =
+        override_properties_in_web_happ(plenty_happ_bundle(), progenitor.clone(), club_settings)
+            .await?;
 
     app.holochain()?
         .install_web_app(String::from(APP_ID), bundle, HashMap::new(), None, None)
@@ -100,12 +113,21 @@ async fn internal_join_plenty_instance(
     Ok(())
 }
 
-async fn override_progenitor_in_web_happ(
+async fn override_properties_in_web_happ(
     web_app_bundle: WebAppBundle,
     progenitor: AgentPubKey,
+    club_settings: Option<ClubSettings>,
 ) -> anyhow::Result<WebAppBundle> {
-    let happ_bundle =
-        override_properties_in_happ(web_app_bundle.happ_bundle().await?, progenitor).await?;
+    if let Some(club_settings) = &club_settings {
+        club_settings.validate().map_err(|err| anyhow!(err))?;
+    }
+
+    let happ_bundle = override_properties_in_happ(
+        web_app_bundle.happ_bundle().await?,
+        progenitor,
+        club_settings,
+    )
+    .await?;
 
     let tempdir = TempDir::new("plenty-webhapp")?;
     let ui_path = tempdir.path().join("ui.zip");
@@ -132,9 +154,22 @@ happ_manifest:
     Ok(bundle)
 }
 
+/// The DNA properties of the plenty role
+///
+/// `club_settings` is left out when not given, so that clubs created before it existed
+/// keep the same DNA hash and can still be joined
+#[derive(Serialize)]
+struct PlentyProperties {
+    #[serde(flatten)]
+    roles: roles_types::Properties,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    club_settings: Option<ClubSettings>,
+}
+
 async fn override_properties_in_happ(
     app_bundle: AppBundle,
     progenitor: AgentPubKey,
+    club_settings: Option<ClubSettings>,
 ) -> anyhow::Result<AppBundle> {
     let inner = app_bundle.into_inner();
 
@@ -144,8 +179,11 @@ async fn override_properties_in_happ(
         AppManifest::V1(v1) => {
             for app_manifest_role in &mut v1.roles {
                 if app_manifest_role.name.as_str().eq("plenty") {
-                    let properties = roles_types::Properties {
-                        progenitors: vec![progenitor.clone().into()],
+                    let properties = PlentyProperties {
+                        roles: roles_types::Properties {
+                            progenitors: vec![progenitor.clone().into()],
+                        },
+                        club_settings: club_settings.clone(),
                     };
 
                     let value = serde_yaml::to_value(properties)?;
@@ -7,6 +7,8 @@ use tauri_plugin_holochain::{HolochainExt, HolochainPluginConfig, WANNetworkConf
 use tauri_plugin_log::Target;
 
 mod commands;
+mod packing_list;
+mod product_catalog;
 
 const APP_ID: &'static str = "plenty";
 
@@ -36,7 +38,9 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             commands::create_plenty_instance,
             commands::join_plenty_instance,
-            commands::leave_buyers_club
+            commands::leave_buyers_club,
+            packing_list::export_packing_list,
+            product_catalog::parse_product_catalog
         ])
         .setup(|app| {
             if let Err(err) = app.deep_link().register("plenty") {
@@ -0,0 +1,305 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use serde::Deserialize;
+use tauri::{AppHandle, Manager};
+
+/// Mirrors the `PackingList` returned by the `get_packing_list` extern of the orders zome,
+/// leaving out the hashes, which are not needed to print it
+#[derive(Deserialize, Debug)]
+pub struct PackingList {
+    pub households: Vec<HouseholdPackingList>,
+    pub producers: Vec<ProducerPickingList>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HouseholdPackingList {
+    pub household_name: String,
+    pub lines: Vec<PackingListLine>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ProducerPickingList {
+    pub producer_name: String,
+    pub lines: Vec<PackingListLine>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PackingListLine {
+    pub product_name: String,
+    pub packaging: Packaging,
+    pub ordered_amount: u32,
+    pub delivered_amount: Option<f32>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Packaging {
+    pub unit: String,
+    pub number_of_packages: u32,
+    pub amount_per_package: f32,
+    pub estimate: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PackingListFormat {
+    Csv,
+    Html,
+}
+
+impl PackingListFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            PackingListFormat::Csv => "csv",
+            PackingListFormat::Html => "html",
+        }
+    }
+}
+
+fn packaging_label(packaging: &Packaging) -> String {
+    let amount = format!(
+        "{} x {} {}",
+        packaging.number_of_packages, packaging.amount_per_package, packaging.unit
+    );
+    if packaging.estimate {
+        format!("~{amount}")
+    } else {
+        amount
+    }
+}
+
+fn delivered_label(delivered_amount: Option<f32>) -> String {
+    delivered_amount
+        .map(|amount| format!("{amount}"))
+        .unwrap_or_default()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    let fields: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+    format!("{}\n", fields.join(","))
+}
+
+/// One row per line, with the household or the producer it belongs to in the first two columns
+pub fn packing_list_to_csv(packing_list: &PackingList) -> String {
+    let mut csv = csv_row(&[
+        "list",
+        "name",
+        "product",
+        "packaging",
+        "ordered_amount",
+        "delivered_amount",
+    ]);
+
+    let lists = packing_list
+        .households
+        .iter()
+        .map(|household| ("household", &household.household_name, &household.lines))
+        .chain(
+            packing_list
+                .producers
+                .iter()
+                .map(|producer| ("producer", &producer.producer_name, &producer.lines)),
+        );
+    for (list, name, lines) in lists {
+        for line in lines {
+            csv.push_str(&csv_row(&[
+                list,
+                name,
+                &line.product_name,
+                &packaging_label(&line.packaging),
+                &line.ordered_amount.to_string(),
+                &delivered_label(line.delivered_amount),
+            ]));
+        }
+    }
+    csv
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html_table(title: &str, lines: &[PackingListLine]) -> String {
+    let rows: String = lines
+        .iter()
+        .map(|line| {
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td></td></tr>",
+                escape_html(&line.product_name),
+                escape_html(&packaging_label(&line.packaging)),
+                line.ordered_amount,
+                delivered_label(line.delivered_amount),
+            )
+        })
+        .collect();
+    format!(
+        r#"<section><h2>{}</h2><table><thead><tr><th>Product</th><th>Packaging</th><th>Ordered</th><th>Delivered</th><th>Packed</th></tr></thead><tbody>{rows}</tbody></table></section>"#,
+        escape_html(title)
+    )
+}
+
+/// Printable page with one table per household, each starting on a new page,
+/// followed by the picking list for each producer
+pub fn packing_list_to_html(order_name: &str, packing_list: &PackingList) -> String {
+    let households: String = packing_list
+        .households
+        .iter()
+        .map(|household| html_table(&household.household_name, &household.lines))
+        .collect();
+    let producers: String = packing_list
+        .producers
+        .iter()
+        .map(|producer| html_table(&producer.producer_name, &producer.lines))
+        .collect();
+    let title = escape_html(order_name);
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+<style>
+  body {{ font-family: sans-serif; }}
+  section {{ break-inside: avoid; page-break-after: always; }}
+  table {{ border-collapse: collapse; width: 100%; }}
+  th, td {{ border: 1px solid #888; padding: 4px 8px; text-align: left; }}
+</style>
+</head>
+<body>
+<h1>{title}</h1>
+{households}
+<h1>Picking lists</h1>
+{producers}
+</body>
+</html>
+"#
+    )
+}
+
+fn file_name(order_name: &str, format: PackingListFormat) -> String {
+    let order_name: String = order_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    format!("packing-list-{order_name}.{}", format.extension())
+}
+
+#[tauri::command]
+pub fn export_packing_list(
+    app: AppHandle,
+    order_name: String,
+    packing_list: PackingList,
+    format: PackingListFormat,
+) -> Result<PathBuf, String> {
+    internal_export_packing_list(app, order_name, packing_list, format)
+        .map_err(|err| format!("{:?}", err))
+}
+
+/// Saves the packing list to the downloads folder, and returns the path of the saved file
+fn internal_export_packing_list(
+    app: AppHandle,
+    order_name: String,
+    packing_list: PackingList,
+    format: PackingListFormat,
+) -> anyhow::Result<PathBuf> {
+    let contents = match format {
+        PackingListFormat::Csv => packing_list_to_csv(&packing_list),
+        PackingListFormat::Html => packing_list_to_html(&order_name, &packing_list),
+    };
+
+    let download_dir = app
+        .path()
+        .download_dir()
+        .map_err(|err| anyhow!("Could not find the downloads folder: {err:?}"))?;
+    let path = download_dir.join(file_name(&order_name, format));
+    std::fs::write(&path, contents)?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(product_name: &str, estimate: bool, delivered_amount: Option<f32>) -> PackingListLine {
+        PackingListLine {
+            product_name: String::from(product_name),
+            packaging: Packaging {
+                unit: String::from("Kilograms"),
+                number_of_packages: 1,
+                amount_per_package: 0.5,
+                estimate,
+            },
+            ordered_amount: 2,
+            delivered_amount,
+        }
+    }
+
+    fn packing_list() -> PackingList {
+        PackingList {
+            households: vec![HouseholdPackingList {
+                household_name: String::from("Smith, \"The Kitchen\""),
+                lines: vec![line("Carrots <organic>", true, Some(1.25))],
+            }],
+            producers: vec![ProducerPickingList {
+                producer_name: String::from("Farm & Co"),
+                lines: vec![line("Carrots <organic>", false, None)],
+            }],
+        }
+    }
+
+    #[test]
+    fn csv_fields_with_separators_or_quotes_are_quoted() {
+        assert_eq!(csv_field("Carrots"), "Carrots");
+        assert_eq!(csv_field("Carrots, organic"), "\"Carrots, organic\"");
+        assert_eq!(
+            csv_field("The \"best\" carrots"),
+            "\"The \"\"best\"\" carrots\""
+        );
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn html_special_characters_are_escaped() {
+        assert_eq!(
+            escape_html("<b>Farm & Co</b> \"organic\""),
+            "&lt;b&gt;Farm &amp; Co&lt;/b&gt; &quot;organic&quot;"
+        );
+    }
+
+    #[test]
+    fn csv_has_one_row_per_household_and_producer_line() {
+        assert_eq!(
+            packing_list_to_csv(&packing_list()),
+            "list,name,product,packaging,ordered_amount,delivered_amount\n\
+             household,\"Smith, \"\"The Kitchen\"\"\",Carrots <organic>,~1 x 0.5 Kilograms,2,1.25\n\
+             producer,Farm & Co,Carrots <organic>,1 x 0.5 Kilograms,2,\n"
+        );
+    }
+
+    #[test]
+    fn html_escapes_the_order_households_producers_and_products() {
+        let html = packing_list_to_html("Week <1>", &packing_list());
+
+        assert!(html.contains("<title>Week &lt;1&gt;</title>"));
+        assert!(html.contains("<h2>Smith, &quot;The Kitchen&quot;</h2>"));
+        assert!(html.contains("<h2>Farm &amp; Co</h2>"));
+        assert!(html.contains(
+            "<tr><td>Carrots &lt;organic&gt;</td><td>~1 x 0.5 Kilograms</td><td>2</td><td>1.25</td><td></td></tr>"
+        ));
+        assert!(!html.contains("<organic>"));
+        assert!(!html.contains("Week <1>"));
+    }
+}
@@ -0,0 +1,8 @@
+use catalog_import::{parse_catalog, CatalogImport};
+
+/// Parses a product catalog CSV, reporting the errors of each row,
+/// so that it can be reviewed before importing its products
+#[tauri::command]
+pub fn parse_product_catalog(csv: String) -> CatalogImport {
+    parse_catalog(&csv)
+}
@@ -35,5 +35,16 @@ test("create a Order and get all orders", async () => {
     collectionOutput = await toPromise(bob.orders.allOrders);
     assert.equal(collectionOutput.size, 1);
     assert.deepEqual(order.actionHash, Array.from(collectionOutput.keys())[0]);
+
+    // Bob gets the orders by status and by month
+    let preparingOrders = await bob.orders.client.getOrdersByStatus("Preparing");
+    assert.equal(preparingOrders.length, 1);
+    let openOrders = await bob.orders.client.getOrdersByStatus("Open");
+    assert.equal(openOrders.length, 0);
+    const ordersInRange = await bob.orders.client.getOrdersInRange(
+      order.action.timestamp - 1,
+      order.action.timestamp + 1,
+    );
+    assert.equal(ordersInRange.length, 1);
   });
 });
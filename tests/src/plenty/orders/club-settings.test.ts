@@ -0,0 +1,48 @@
+import { assert, test } from "vitest";
+
+import { runScenario } from "@holochain/tryorama";
+
+import { setup } from "../../setup.js";
+
+test("clubs without settings in their DNA properties use euros with 2 decimals", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    assert.deepEqual(await alice.orders.client.getClubSettings(), {
+      currency_code: "EUR",
+      decimal_places: 2,
+    });
+    assert.deepEqual(await bob.orders.client.getClubSettings(), {
+      currency_code: "EUR",
+      decimal_places: 2,
+    });
+  });
+});
+
+test("get_club_settings returns the settings the club was created with", async () => {
+  await runScenario(async (scenario) => {
+    const { bob } = await setup(scenario, {
+      currency_code: "JPY",
+      decimal_places: 0,
+    });
+
+    assert.deepEqual(await bob.orders.client.getClubSettings(), {
+      currency_code: "JPY",
+      decimal_places: 0,
+    });
+  });
+});
+
+test("clubs with out of range settings fall back to the default settings", async () => {
+  await runScenario(async (scenario) => {
+    const { alice } = await setup(scenario, {
+      currency_code: "XXX",
+      decimal_places: 25,
+    });
+
+    assert.deepEqual(await alice.orders.client.getClubSettings(), {
+      currency_code: "EUR",
+      decimal_places: 2,
+    });
+  });
+});
@@ -0,0 +1,200 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { encodeHashToBase64 } from "@holochain/client";
+
+import {
+  DeliveryInconsistency,
+  ProducerDelivery,
+} from "../../../../ui/src/plenty/orders/types.js";
+import { sampleOpenOrderOfferingProducts } from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("validate_delivery_consistency reports deliveries that don't match the household orders", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const orderedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        maximum_available: undefined,
+      }),
+    );
+    const notOrderedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        maximum_available: undefined,
+      }),
+    );
+    const order = await sampleOpenOrderOfferingProducts(alice.orders.client, [
+      orderedProduct,
+      notOrderedProduct,
+    ]);
+
+    const aliceHousehold = await alice.households.client.createHousehold(
+      await sampleHousehold(alice.households.client, {
+        name: "Alice's Household",
+      }),
+    );
+    const bobHousehold = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const latestOrder = await bob.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+    await bob.orders.client.createHouseholdOrder({
+      order_hash: order.actionHash,
+      latest_order_hash: latestOrder!.actionHash,
+      household_hash: bobHousehold.actionHash,
+      products: [
+        {
+          original_product_hash: orderedProduct.actionHash,
+          ordered_product_hash: orderedProduct.actionHash,
+          amount: 2,
+        },
+      ],
+    });
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const delivery = (products: ProducerDelivery["products"]) =>
+      alice.orders.client.createProducerDelivery({
+        order_hash: order.actionHash,
+        producer_hash: producer.actionHash,
+        latest_producer_hash: producer.actionHash,
+        products,
+      });
+
+    // A delivery of exactly what Bob's household ordered is consistent
+    const producerDelivery = await delivery({
+      [encodeHashToBase64(orderedProduct.actionHash)]: {
+        type: "Delivered",
+        delivered_amount: {
+          type: "FixedAmountProduct",
+          delivered_products: [
+            {
+              amount: 2,
+              households_hashes: [bobHousehold.actionHash],
+            },
+          ],
+          price_cents_per_unit_changed: undefined,
+        },
+        comment: undefined,
+      },
+    });
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    let inconsistencies = await bob.orders.client.validateDeliveryConsistency(
+      order.actionHash,
+    );
+    assert.deepEqual(inconsistencies, []);
+
+    // The producer corrects the delivery: one more unit, for Alice's household
+    // that didn't order it, and a product that nobody ordered
+    const correctedDelivery = await alice.orders.client.updateProducerDelivery(
+      producerDelivery.actionHash,
+      {
+        ...producerDelivery.entry,
+        products: {
+          [encodeHashToBase64(orderedProduct.actionHash)]: {
+            type: "Delivered",
+            delivered_amount: {
+              type: "FixedAmountProduct",
+              delivered_products: [
+                {
+                  amount: 2,
+                  households_hashes: [bobHousehold.actionHash],
+                },
+                {
+                  amount: 1,
+                  households_hashes: [aliceHousehold.actionHash],
+                },
+              ],
+              price_cents_per_unit_changed: undefined,
+            },
+            comment: undefined,
+          },
+          [encodeHashToBase64(notOrderedProduct.actionHash)]: {
+            type: "Delivered",
+            delivered_amount: {
+              type: "FixedAmountProduct",
+              delivered_products: [
+                {
+                  amount: 1,
+                  households_hashes: [bobHousehold.actionHash],
+                },
+              ],
+              price_cents_per_unit_changed: undefined,
+            },
+            comment: undefined,
+          },
+        },
+      },
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Only the latest revision of the delivery is checked
+    inconsistencies = await bob.orders.client.validateDeliveryConsistency(
+      order.actionHash,
+    );
+    assert.equal(inconsistencies.length, 3);
+    for (const inconsistency of inconsistencies) {
+      assert.deepEqual(
+        inconsistency.producer_delivery_hash,
+        correctedDelivery.actionHash,
+      );
+    }
+
+    const notOrdered = inconsistencies.find(
+      (i) => i.type === "ProductNotOrdered",
+    );
+    assert.deepEqual(
+      notOrdered?.original_product_hash,
+      notOrderedProduct.actionHash,
+    );
+
+    const householdDidNotOrder = inconsistencies.find(
+      (i) => i.type === "HouseholdDidNotOrderProduct",
+    ) as Extract<DeliveryInconsistency, { type: "HouseholdDidNotOrderProduct" }>;
+    assert.ok(householdDidNotOrder);
+    assert.deepEqual(
+      householdDidNotOrder.original_product_hash,
+      orderedProduct.actionHash,
+    );
+    assert.deepEqual(
+      householdDidNotOrder.household_hash,
+      aliceHousehold.actionHash,
+    );
+
+    const deliveredMore = inconsistencies.find(
+      (i) => i.type === "DeliveredMoreThanOrdered",
+    ) as Extract<DeliveryInconsistency, { type: "DeliveredMoreThanOrdered" }>;
+    assert.ok(deliveredMore);
+    assert.deepEqual(
+      deliveredMore.original_product_hash,
+      orderedProduct.actionHash,
+    );
+    assert.equal(deliveredMore.ordered_amount, 2);
+    assert.equal(deliveredMore.delivered_amount, 3);
+  });
+});
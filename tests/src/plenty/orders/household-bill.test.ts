@@ -0,0 +1,205 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { encodeHashToBase64 } from "@holochain/client";
+
+import {
+  sampleAvailableProducts,
+  sampleOrder,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("household bills add up the delivered amounts, changed prices, margin and VAT", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    // Sold by the piece, at 2.50 with 10% VAT and no margin
+    const fixedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        name: "Eggs",
+        maximum_available: undefined,
+        price_cents: 250,
+        vat_percentage: 10,
+        margin_percentage: undefined,
+      }),
+    );
+    // Sold by the 500 g bag at 4.00, with a 10% margin and 5.5% VAT, but billed by weight
+    const estimatedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        name: "Potatoes",
+        packaging: {
+          unit: "Kilograms",
+          number_of_packages: 1,
+          amount_per_package: 0.5,
+          estimate: true,
+        },
+        maximum_available: undefined,
+        price_cents: 400,
+        vat_percentage: 5.5,
+        margin_percentage: 10,
+      }),
+    );
+
+    const order = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+    const availableProducts = await alice.orders.client.createAvailableProducts(
+      await sampleAvailableProducts(
+        alice.orders.client,
+        producer.actionHash,
+        producer.actionHash,
+        {
+          order_hash: order.actionHash,
+          producer_availability: {
+            type: "Available",
+            available_products: [
+              fixedProduct.actionHash,
+              estimatedProduct.actionHash,
+            ],
+          },
+        },
+      ),
+    );
+    await alice.orders.client.updateOrder(order.actionHash, order.actionHash, {
+      ...order.entry,
+      status: {
+        type: "Open",
+        deadline: (Date.now() + 60 * 60 * 1000) * 1000,
+        available_products: [availableProducts.actionHash],
+      },
+    });
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const latestOrder = await bob.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+    await bob.orders.client.createHouseholdOrder({
+      order_hash: order.actionHash,
+      latest_order_hash: latestOrder!.actionHash,
+      household_hash: household.actionHash,
+      products: [
+        {
+          original_product_hash: fixedProduct.actionHash,
+          ordered_product_hash: fixedProduct.actionHash,
+          amount: 3,
+        },
+        {
+          original_product_hash: estimatedProduct.actionHash,
+          ordered_product_hash: estimatedProduct.actionHash,
+          amount: 2,
+        },
+      ],
+    });
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Before the delivery, the household is billed by what it ordered
+    let bill = await bob.orders.client.computeHouseholdBill(
+      order.actionHash,
+      household.actionHash,
+    );
+    // 3 * 250 + 10% VAT, and 2 * 400 + 10% margin + 5.5% VAT
+    assert.equal(bill.lines[0].delivered_amount, undefined);
+    assert.equal(bill.lines[0].total_cents, 825);
+    assert.equal(bill.lines[1].total_cents, 800 + 80 + 48);
+    assert.equal(bill.total_cents, 825 + 928);
+
+    // The producer delivers the eggs at 2.75 each, and 1.25 kg of potatoes
+    await alice.orders.client.createProducerDelivery({
+      order_hash: order.actionHash,
+      producer_hash: producer.actionHash,
+      latest_producer_hash: producer.actionHash,
+      products: {
+        [encodeHashToBase64(fixedProduct.actionHash)]: {
+          type: "Delivered",
+          delivered_amount: {
+            type: "FixedAmountProduct",
+            delivered_products: [
+              {
+                amount: 3,
+                households_hashes: [household.actionHash],
+              },
+            ],
+            price_cents_per_unit_changed: 275,
+          },
+          comment: undefined,
+        },
+        [encodeHashToBase64(estimatedProduct.actionHash)]: {
+          type: "Delivered",
+          delivered_amount: {
+            type: "EstimatedAmountProduct",
+            delivered_products_by_household: [
+              {
+                products: [0.75, 0.5],
+                households_hashes: [household.actionHash],
+              },
+            ],
+            price_cents_per_unit_changed: undefined,
+          },
+          comment: undefined,
+        },
+      },
+    });
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    bill = await bob.orders.client.computeHouseholdBill(
+      order.actionHash,
+      household.actionHash,
+    );
+
+    const [fixedLine, estimatedLine] = bill.lines;
+    // 3 * 275 = 825, plus 82.5 of VAT rounded to 83
+    assert.equal(fixedLine.delivered_amount, 3);
+    assert.equal(fixedLine.price_cents_per_unit, 275);
+    assert.ok(fixedLine.price_changed);
+    assert.equal(fixedLine.subtotal_cents, 825);
+    assert.equal(fixedLine.margin_cents, 0);
+    assert.equal(fixedLine.vat_cents, 83);
+    assert.equal(fixedLine.total_cents, 908);
+
+    // 1.25 kg are 2.5 bags: 1000, plus a margin of 100 and 60.5 of VAT rounded to 61
+    assert.equal(estimatedLine.delivered_amount, 1.25);
+    assert.equal(estimatedLine.price_cents_per_unit, 400);
+    assert.notOk(estimatedLine.price_changed);
+    assert.equal(estimatedLine.subtotal_cents, 1000);
+    assert.equal(estimatedLine.margin_cents, 100);
+    assert.equal(estimatedLine.vat_cents, 61);
+    assert.equal(estimatedLine.total_cents, 1161);
+
+    assert.equal(bill.subtotal_cents, 1825);
+    assert.equal(bill.margin_cents, 100);
+    assert.equal(bill.vat_cents, 144);
+    assert.equal(bill.total_cents, 2069);
+
+    // The bookkeepers' bill table has the same bill for the only household in the order
+    const orderBill = await alice.orders.client.computeOrderBill(
+      order.actionHash,
+    );
+    assert.equal(orderBill.households.length, 1);
+    assert.deepEqual(orderBill.households[0].lines, bill.lines);
+    assert.equal(orderBill.total_cents, 2069);
+  });
+});
@@ -1,6 +1,6 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
-import { runScenario, dhtSync } from "@holochain/tryorama";
+import { runScenario, dhtSync, pause } from "@holochain/tryorama";
 import {
   ActionHash,
   SignedActionHashed,
@@ -12,8 +12,20 @@ import { EntryRecord } from "@holochain-open-dev/utils";
 import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-decoding.js";
 import { toPromise } from "@holochain-open-dev/signals";
 
-import { HouseholdOrder } from "../../../../ui/src/plenty/orders/types.js";
-import { sampleHouseholdOrder } from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  HouseholdOrder,
+  ProductOrder,
+} from "../../../../ui/src/plenty/orders/types.js";
+import {
+  sampleAvailableProducts,
+  sampleHouseholdOrder,
+  sampleOpenOrder,
+  sampleOrder,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
 import { setup } from "../../setup.js";
 import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
 import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
@@ -191,3 +203,315 @@ test("create and delete HouseholdOrder", async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test("household orders after the deadline are rejected, except from order managers", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const order = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+    await alice.orders.client.updateOrder(
+      order.actionHash,
+      order.actionHash,
+      {
+        ...order.entry,
+        status: {
+          type: "Open",
+          deadline: (Date.now() + 10_000) * 1000,
+          available_products: [],
+        },
+      },
+    );
+
+    const aliceHousehold = await alice.households.client.createHousehold(
+      await sampleHousehold(alice.households.client, {
+        name: "Alice's Household",
+      }),
+    );
+    const bobHousehold = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+    const bobLateHousehold = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Late Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob orders before the deadline
+    const bobHouseholdOrder = await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, bobHousehold.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+
+    await pause(12_000);
+
+    // Bob can't create nor update household orders after the deadline
+    await expect(
+      bob.orders.client.createHouseholdOrder(
+        await sampleHouseholdOrder(
+          bob.orders.client,
+          bobLateHousehold.actionHash,
+          {
+            order_hash: order.actionHash,
+          },
+        ),
+      ),
+    ).rejects.toThrowError(undefined);
+    await expect(
+      bob.orders.client.updateHouseholdOrder(
+        bobHouseholdOrder.actionHash,
+        bobHouseholdOrder.actionHash,
+        bobHouseholdOrder.entry,
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Alice, as an order manager, can still make late corrections,
+    // even to the household orders of households Alice is not a member of
+    await alice.orders.client.updateHouseholdOrder(
+      bobHouseholdOrder.actionHash,
+      bobHouseholdOrder.actionHash,
+      bobHouseholdOrder.entry,
+    );
+    const aliceHouseholdOrder = await alice.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(alice.orders.client, aliceHousehold.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+    assert.ok(aliceHouseholdOrder);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const closedOrder = await alice.orders.client.closeOrder(order.actionHash);
+    assert.equal(closedOrder.entry.status.type, "Closed");
+    const householdOrders = (
+      closedOrder.entry.status as { household_orders: ActionHash[] }
+    ).household_orders;
+    assert.equal(householdOrders.length, 2);
+  });
+});
+
+test("household orders can only order offered products, once and in positive amounts", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const { order, product } = await sampleOpenOrder(alice.orders.client);
+
+    // A product from a producer that is unavailable for this order
+    const otherProducer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const unavailableProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: otherProducer.actionHash,
+      }),
+    );
+    const unavailableProducer = await alice.orders.client.createAvailableProducts(
+      await sampleAvailableProducts(
+        alice.orders.client,
+        otherProducer.actionHash,
+        otherProducer.actionHash,
+        {
+          order_hash: order.actionHash,
+          producer_availability: {
+            type: "Unavailable",
+          },
+        },
+      ),
+    );
+    const latestOrder = await alice.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+    const openStatus = latestOrder!.entry.status as {
+      type: "Open";
+      deadline: number;
+      available_products: ActionHash[];
+    };
+    await alice.orders.client.updateOrder(
+      order.actionHash,
+      latestOrder!.actionHash,
+      {
+        ...latestOrder!.entry,
+        status: {
+          ...openStatus,
+          available_products: [
+            ...openStatus.available_products,
+            unavailableProducer.actionHash,
+          ],
+        },
+      },
+    );
+
+    // A product that is not offered in this order at all
+    const notOfferedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: product.entry.producer_hash,
+      }),
+    );
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const productOrder = (productHash: ActionHash, amount: number) => ({
+      original_product_hash: productHash,
+      ordered_product_hash: productHash,
+      amount,
+    });
+    const householdOrder = (products: Array<ProductOrder>) =>
+      sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: order.actionHash,
+        products,
+      });
+
+    // Not offered in the order
+    await expect(
+      bob.orders.client.createHouseholdOrder(
+        await householdOrder([productOrder(notOfferedProduct.actionHash, 1)]),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Its producer is unavailable for the order
+    await expect(
+      bob.orders.client.createHouseholdOrder(
+        await householdOrder([productOrder(unavailableProduct.actionHash, 1)]),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Zero amount
+    await expect(
+      bob.orders.client.createHouseholdOrder(
+        await householdOrder([productOrder(product.actionHash, 0)]),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Duplicate line
+    await expect(
+      bob.orders.client.createHouseholdOrder(
+        await householdOrder([
+          productOrder(product.actionHash, 1),
+          productOrder(product.actionHash, 1),
+        ]),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    const validHouseholdOrder = await bob.orders.client.createHouseholdOrder(
+      await householdOrder([productOrder(product.actionHash, 1)]),
+    );
+    assert.ok(validHouseholdOrder);
+  });
+});
+
+test("closing an order snapshots only the latest revision of each household order", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const { order, product } = await sampleOpenOrder(
+      alice.orders.client,
+      {},
+      10_000,
+    );
+    const { order: otherOrder } = await sampleOpenOrder(alice.orders.client);
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const householdOrder = await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+    const updatedHouseholdOrder = await bob.orders.client.updateHouseholdOrder(
+      householdOrder.actionHash,
+      householdOrder.actionHash,
+      {
+        ...householdOrder.entry,
+        products: [
+          {
+            original_product_hash: product.actionHash,
+            ordered_product_hash: product.actionHash,
+            amount: 1,
+          },
+        ],
+      },
+    );
+    const otherHouseholdOrder = await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: otherOrder.actionHash,
+      }),
+    );
+
+    await pause(12_000);
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const latestOrder = await alice.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+
+    // Both revisions of the same household order
+    await expect(
+      alice.orders.client.updateOrder(
+        order.actionHash,
+        latestOrder!.actionHash,
+        {
+          ...latestOrder!.entry,
+          status: {
+            type: "Closed",
+            household_orders: [
+              householdOrder.actionHash,
+              updatedHouseholdOrder.actionHash,
+            ],
+          },
+        },
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // A household order from another order
+    await expect(
+      alice.orders.client.updateOrder(
+        order.actionHash,
+        latestOrder!.actionHash,
+        {
+          ...latestOrder!.entry,
+          status: {
+            type: "Closed",
+            household_orders: [otherHouseholdOrder.actionHash],
+          },
+        },
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Closing the order skips the stale revision
+    const closedOrder = await alice.orders.client.closeOrder(order.actionHash);
+    const householdOrders = (
+      closedOrder.entry.status as { household_orders: ActionHash[] }
+    ).household_orders;
+    assert.deepEqual(householdOrders, [updatedHouseholdOrder.actionHash]);
+  });
+});
@@ -0,0 +1,125 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { EntryRecord } from "@holochain-open-dev/utils";
+import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-decoding.js";
+
+import { HouseholdPayment } from "../../../../ui/src/plenty/orders/types.js";
+import { sampleHouseholdPayment } from "../../../../ui/src/plenty/orders/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import {
+  bookkeeperRoleConfig,
+  orderManagerRoleConfig,
+} from "../../../../ui/src/roles.js";
+
+test("create, update and delete HouseholdPayment", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    await alice.roles.client.assignRole(bookkeeperRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const household = await alice.households.client.createHousehold(
+      await sampleHousehold(alice.households.client, {
+        name: "My Household",
+      }),
+    );
+
+    const sample = await sampleHouseholdPayment(
+      alice.orders.client,
+      household.actionHash,
+    );
+
+    // Alice creates a HouseholdPayment
+    const householdPayment: EntryRecord<HouseholdPayment> =
+      await alice.orders.client.createHouseholdPayment(sample);
+    assert.ok(householdPayment);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob gets the HouseholdPayments for the order and for the household
+    let links = await bob.orders.client.getHouseholdPaymentsForOrder(
+      sample.order_hash,
+    );
+    assert.equal(links.length, 1);
+    links = await bob.orders.client.getHouseholdPaymentsForHousehold(
+      household.actionHash,
+    );
+    assert.equal(links.length, 1);
+    let latest = await bob.orders.client.getLatestHouseholdPayment(
+      householdPayment.actionHash,
+    );
+    assert.deepEqual(sample, cleanNodeDecoding(latest!.entry));
+
+    // Bob is not a bookkeeper, so they can't create, update or delete payments
+    await expect(() =>
+      bob.orders.client.createHouseholdPayment(sample),
+    ).rejects.toThrowError(undefined);
+    await expect(() =>
+      bob.orders.client.updateHouseholdPayment(householdPayment.actionHash, {
+        ...sample,
+        amount_cents: 1,
+      }),
+    ).rejects.toThrowError(undefined);
+    await expect(() =>
+      bob.orders.client.deleteHouseholdPayment(householdPayment.actionHash),
+    ).rejects.toThrowError(undefined);
+
+    // Alice can't move the payment to another order
+    const otherPayment = await sampleHouseholdPayment(
+      alice.orders.client,
+      household.actionHash,
+    );
+    await expect(() =>
+      alice.orders.client.updateHouseholdPayment(
+        householdPayment.actionHash,
+        otherPayment,
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // Alice corrects the payment
+    const contentUpdate: HouseholdPayment = {
+      ...sample,
+      amount_cents: 1300,
+      payment_method: {
+        type: "Other",
+        description: "Voucher",
+      },
+    };
+    await alice.orders.client.updateHouseholdPayment(
+      householdPayment.actionHash,
+      contentUpdate,
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    latest = await bob.orders.client.getLatestHouseholdPayment(
+      householdPayment.actionHash,
+    );
+    assert.deepEqual(contentUpdate, cleanNodeDecoding(latest!.entry));
+
+    // Alice deletes the payment
+    await alice.orders.client.deleteHouseholdPayment(
+      householdPayment.actionHash,
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const deleteAction =
+      await bob.orders.client.getOldestDeleteForHouseholdPayment(
+        householdPayment.actionHash,
+      );
+    assert.ok(deleteAction);
+    links = await bob.orders.client.getHouseholdPaymentsForOrder(
+      sample.order_hash,
+    );
+    assert.equal(links.length, 0);
+    links = await bob.orders.client.getHouseholdPaymentsForHousehold(
+      household.actionHash,
+    );
+    assert.equal(links.length, 0);
+  });
+});
@@ -0,0 +1,104 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { fakeActionHash } from "@holochain/client";
+import { EntryRecord } from "@holochain-open-dev/utils";
+import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-decoding.js";
+
+import { HouseholdPickup } from "../../../../ui/src/plenty/orders/types.js";
+import { sampleHouseholdPickup } from "../../../../ui/src/plenty/orders/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("create and update HouseholdPickup", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const household = await alice.households.client.createHousehold(
+      await sampleHousehold(alice.households.client, {
+        name: "My Household",
+      }),
+    );
+
+    const sample = await sampleHouseholdPickup(
+      alice.orders.client,
+      household.actionHash,
+    );
+
+    // Alice can only record the pickups that they handed out
+    await expect(() =>
+      alice.orders.client.createHouseholdPickup({
+        ...sample,
+        handed_out_by: bob.player.agentPubKey,
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // Alice creates a HouseholdPickup
+    const householdPickup: EntryRecord<HouseholdPickup> =
+      await alice.orders.client.createHouseholdPickup(sample);
+    assert.ok(householdPickup);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob gets the HouseholdPickups for the order
+    const links = await bob.orders.client.getHouseholdPickupsForOrder(
+      sample.order_hash,
+    );
+    assert.equal(links.length, 1);
+    const latest = await bob.orders.client.getLatestHouseholdPickup(
+      householdPickup.actionHash,
+    );
+    assert.deepEqual(sample, cleanNodeDecoding(latest!.entry));
+
+    // Bob is neither a member of the household nor an order manager, so they can't update it
+    await expect(() =>
+      bob.orders.client.updateHouseholdPickup(householdPickup.actionHash, {
+        ...sample,
+        handed_out_by: bob.player.agentPubKey,
+        exceptions: [],
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // Alice records that one of the products was missing from the bag
+    const contentUpdate: HouseholdPickup = {
+      ...sample,
+      exceptions: [
+        {
+          original_product_hash: await fakeActionHash(),
+          amount: 1,
+          kind: { type: "Missing" },
+          comment: "Not in the bag",
+        },
+      ],
+    };
+    const updatedHouseholdPickup =
+      await alice.orders.client.updateHouseholdPickup(
+        householdPickup.actionHash,
+        contentUpdate,
+      );
+    assert.ok(updatedHouseholdPickup);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const updated = await bob.orders.client.getLatestHouseholdPickup(
+      householdPickup.actionHash,
+    );
+    assert.deepEqual(contentUpdate, cleanNodeDecoding(updated!.entry));
+
+    // Bob can't delete it either, but Alice can
+    await expect(() =>
+      bob.orders.client.deleteHouseholdPickup(householdPickup.actionHash),
+    ).rejects.toThrowError(undefined);
+    await alice.orders.client.deleteHouseholdPickup(householdPickup.actionHash);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const linksAfterDelete = await bob.orders.client.getHouseholdPickupsForOrder(
+      sample.order_hash,
+    );
+    assert.equal(linksAfterDelete.length, 0);
+  });
+});
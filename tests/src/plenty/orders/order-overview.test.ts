@@ -0,0 +1,122 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+
+import {
+  sampleHouseholdOrder,
+  sampleOpenOrder,
+  sampleProducerDelivery,
+  sampleProducerInvoice,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import {
+  bookkeeperRoleConfig,
+  orderManagerRoleConfig,
+} from "../../../../ui/src/roles.js";
+
+test("get_order_overview returns the latest revision of everything in the order", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    await alice.roles.client.assignRole(bookkeeperRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const { order, product } = await sampleOpenOrder(alice.orders.client);
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob orders, and then changes the household order
+    const householdOrder = await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+    const updatedHouseholdOrder = await bob.orders.client.updateHouseholdOrder(
+      householdOrder.actionHash,
+      householdOrder.actionHash,
+      {
+        ...householdOrder.entry,
+        products: [
+          {
+            original_product_hash: product.actionHash,
+            ordered_product_hash: product.actionHash,
+            amount: 1,
+          },
+        ],
+      },
+    );
+
+    // Alice records the producer delivery twice, and the producer invoice
+    const producerDelivery = await alice.orders.client.createProducerDelivery(
+      await sampleProducerDelivery(
+        alice.orders.client,
+        product.entry.producer_hash,
+        {
+          order_hash: order.actionHash,
+        },
+      ),
+    );
+    const updatedProducerDelivery =
+      await alice.orders.client.updateProducerDelivery(
+        producerDelivery.actionHash,
+        producerDelivery.entry,
+      );
+    const producerInvoice = await alice.orders.client.createProducerInvoice(
+      await sampleProducerInvoice(
+        alice.orders.client,
+        product.entry.producer_hash,
+        {
+          order_hash: order.actionHash,
+        },
+      ),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const overview = await bob.orders.client.getOrderOverview(
+      order.actionHash,
+    );
+    assert.ok(overview);
+
+    const latestOrder = await bob.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+    assert.deepEqual(overview!.order.actionHash, latestOrder!.actionHash);
+    assert.equal(overview!.order.entry.status.type, "Open");
+
+    assert.equal(overview!.householdOrders.length, 1);
+    assert.deepEqual(
+      overview!.householdOrders[0].actionHash,
+      updatedHouseholdOrder.actionHash,
+    );
+
+    assert.equal(overview!.availableProducts.length, 1);
+    assert.deepEqual(
+      overview!.availableProducts[0].entry.original_producer_hash,
+      product.entry.producer_hash,
+    );
+
+    assert.equal(overview!.producerDeliveries.length, 1);
+    assert.deepEqual(
+      overview!.producerDeliveries[0].actionHash,
+      updatedProducerDelivery.actionHash,
+    );
+
+    assert.equal(overview!.producerInvoices.length, 1);
+    assert.deepEqual(
+      overview!.producerInvoices[0].actionHash,
+      producerInvoice.actionHash,
+    );
+  });
+});
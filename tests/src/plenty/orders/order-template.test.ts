@@ -0,0 +1,123 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+
+import {
+  sampleAvailableProducts,
+  sampleOrder,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("create an order from a template drops the deleted products and producers", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const keptProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+      }),
+    );
+    const deletedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+      }),
+    );
+    const deletedProducer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+
+    const template = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+    await alice.orders.client.createAvailableProducts(
+      await sampleAvailableProducts(
+        alice.orders.client,
+        producer.actionHash,
+        producer.actionHash,
+        {
+          order_hash: template.actionHash,
+          producer_availability: {
+            type: "Available",
+            available_products: [
+              keptProduct.actionHash,
+              deletedProduct.actionHash,
+            ],
+          },
+        },
+      ),
+    );
+    await alice.orders.client.createAvailableProducts(
+      await sampleAvailableProducts(
+        alice.orders.client,
+        deletedProducer.actionHash,
+        deletedProducer.actionHash,
+        {
+          order_hash: template.actionHash,
+          producer_availability: {
+            type: "Unavailable",
+          },
+        },
+      ),
+    );
+
+    // Since the template order, the producer has been updated,
+    // one of its products deleted, and the other producer deleted
+    const updatedProducer = await alice.producers.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      {
+        ...producer.entry,
+        name: "Renamed producer",
+      },
+    );
+    await alice.producers.client.deleteProduct(deletedProduct.actionHash);
+    await alice.producers.client.deleteProducer(deletedProducer.actionHash);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const { order, availableProducts, deletedProducts, deletedProducers } =
+      await alice.orders.client.createOrderFromTemplate(
+        template.actionHash,
+        "Next week",
+      );
+
+    assert.equal(order.entry.name, "Next week");
+    assert.equal(order.entry.status.type, "Preparing");
+
+    assert.equal(availableProducts.length, 1);
+    const [copied] = availableProducts;
+    assert.deepEqual(copied.entry.order_hash, order.actionHash);
+    assert.deepEqual(copied.entry.original_producer_hash, producer.actionHash);
+    assert.deepEqual(
+      copied.entry.latest_producer_hash,
+      updatedProducer.actionHash,
+    );
+    assert.deepEqual(copied.entry.producer_availability, {
+      type: "Available",
+      available_products: [keptProduct.actionHash],
+    });
+
+    assert.deepEqual(deletedProducts, [deletedProduct.actionHash]);
+    assert.deepEqual(deletedProducers, [deletedProducer.actionHash]);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob sees the new order with its copied availability
+    const links = await bob.orders.client.getAvailableProductsForOrder(
+      order.actionHash,
+    );
+    assert.equal(links.length, 1);
+  });
+});
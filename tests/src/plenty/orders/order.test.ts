@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync, pause } from "@holochain/tryorama";
 import {
@@ -152,3 +152,132 @@ test("create and delete Order", async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test("Order status can't move backwards nor skip steps", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const order: EntryRecord<Order> = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+
+    // Preparing -> Processed skips the Open and Closed statuses
+    await expect(
+      alice.orders.client.updateOrder(order.actionHash, order.actionHash, {
+        ...order.entry,
+        status: {
+          type: "Processed",
+          producers_deliveries: [],
+        },
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // An Open order needs a deadline in the future
+    await expect(
+      alice.orders.client.updateOrder(order.actionHash, order.actionHash, {
+        ...order.entry,
+        status: {
+          type: "Open",
+          deadline: (Date.now() - 60 * 60 * 1000) * 1000,
+          available_products: [],
+        },
+      }),
+    ).rejects.toThrowError(undefined);
+
+    const deadline = (Date.now() + 10_000) * 1000;
+    const openOrder = await alice.orders.client.updateOrder(
+      order.actionHash,
+      order.actionHash,
+      {
+        ...order.entry,
+        status: {
+          type: "Open",
+          deadline,
+          available_products: [],
+        },
+      },
+    );
+
+    // The deadline of an Open order can't be moved earlier
+    await expect(
+      alice.orders.client.updateOrder(order.actionHash, openOrder.actionHash, {
+        ...order.entry,
+        status: {
+          type: "Open",
+          deadline: deadline - 5_000_000,
+          available_products: [],
+        },
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // Open -> Finished skips the Closed and Processed statuses
+    await expect(
+      alice.orders.client.updateOrder(order.actionHash, openOrder.actionHash, {
+        ...order.entry,
+        status: {
+          type: "Finished",
+          household_payments: [],
+          producers_invoices: [],
+        },
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // An Open order can only be closed after its deadline
+    await expect(
+      alice.orders.client.updateOrder(order.actionHash, openOrder.actionHash, {
+        ...order.entry,
+        status: {
+          type: "Closed",
+          household_orders: [],
+        },
+      }),
+    ).rejects.toThrowError(undefined);
+
+    await pause(12_000);
+
+    const closedOrder = await alice.orders.client.closeOrder(order.actionHash);
+    const processedOrder = await alice.orders.client.updateOrder(
+      order.actionHash,
+      closedOrder.actionHash,
+      {
+        ...order.entry,
+        status: {
+          type: "Processed",
+          producers_deliveries: [],
+        },
+      },
+    );
+    const finishedOrder = await alice.orders.client.updateOrder(
+      order.actionHash,
+      processedOrder.actionHash,
+      {
+        ...order.entry,
+        status: {
+          type: "Finished",
+          household_payments: [],
+          producers_invoices: [],
+        },
+      },
+    );
+
+    // Finished -> Open reopens an order that is already finished
+    await expect(
+      alice.orders.client.updateOrder(
+        order.actionHash,
+        finishedOrder.actionHash,
+        {
+          ...order.entry,
+          status: {
+            type: "Open",
+            deadline: (Date.now() + 60 * 60 * 1000) * 1000,
+            available_products: [],
+          },
+        },
+      ),
+    ).rejects.toThrowError(undefined);
+  });
+});
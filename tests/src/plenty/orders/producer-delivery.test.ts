@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync } from "@holochain/tryorama";
 import {
@@ -13,8 +13,11 @@ import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-dec
 import { toPromise } from "@holochain-open-dev/signals";
 
 import { ProducerDelivery } from "../../../../ui/src/plenty/orders/types.js";
-import { sampleProducerDelivery } from "../../../../ui/src/plenty/orders/mocks.js";
-import { setup } from "../../setup.js";
+import {
+  sampleOrder,
+  sampleProducerDelivery,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import { setup, setupWithThreePlayers } from "../../setup.js";
 import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
 import { sampleProducer } from "../../../../ui/src/plenty/producers/mocks.js";
 
@@ -38,6 +41,31 @@ test("create ProducerDelivery", async () => {
   });
 });
 
+test("only the liason or an order manager can create a ProducerDelivery", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const sample = await sampleProducerDelivery(
+      alice.orders.client,
+      producer.actionHash,
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob is neither the liason of the producer nor an order manager
+    await expect(
+      () => bob.orders.client.createProducerDelivery(sample),
+      "Bob was able to create a ProducerDelivery without being the liason or an order manager",
+    ).rejects.toThrowError(undefined);
+  });
+});
+
 test("create and read ProducerDelivery", async () => {
   await runScenario(async (scenario) => {
     const { alice, bob } = await setup(scenario);
@@ -184,3 +212,131 @@ test("create and delete ProducerDelivery", async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test("only the current liason can change a ProducerDelivery after the liason is reassigned", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob, carol } = await setupWithThreePlayers(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const order = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+
+    // Bob is the liason of the producer
+    const producer = await bob.producers.client.createProducer(
+      await sampleProducer(bob.producers.client),
+    );
+
+    await dhtSync(
+      [alice.player, bob.player, carol.player],
+      alice.player.cells[0].cell_id[0],
+    );
+
+    const producerDelivery = await bob.orders.client.createProducerDelivery(
+      await sampleProducerDelivery(bob.orders.client, producer.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+
+    // Bob hands the producer over to Carol
+    await bob.producers.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      {
+        ...producer.entry,
+        liason: carol.player.agentPubKey,
+      },
+    );
+
+    await dhtSync(
+      [alice.player, bob.player, carol.player],
+      alice.player.cells[0].cell_id[0],
+    );
+
+    await expect(
+      () =>
+        bob.orders.client.updateProducerDelivery(
+          producerDelivery.actionHash,
+          producerDelivery.entry,
+        ),
+      "Bob was able to change a ProducerDelivery after no longer being the liason",
+    ).rejects.toThrowError(undefined);
+
+    const updatedProducerDelivery =
+      await carol.orders.client.updateProducerDelivery(
+        producerDelivery.actionHash,
+        producerDelivery.entry,
+      );
+    assert.ok(updatedProducerDelivery);
+  });
+});
+
+test("a replaced liason can't update the deliveries recorded by the new liason", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob, carol } = await setupWithThreePlayers(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const order = await alice.orders.client.createOrder(
+      await sampleOrder(alice.orders.client),
+    );
+
+    // Bob is the liason of the producer, and records its delivery
+    const producer = await bob.producers.client.createProducer(
+      await sampleProducer(bob.producers.client),
+    );
+
+    await dhtSync(
+      [alice.player, bob.player, carol.player],
+      alice.player.cells[0].cell_id[0],
+    );
+
+    const producerDelivery = await bob.orders.client.createProducerDelivery(
+      await sampleProducerDelivery(bob.orders.client, producer.actionHash, {
+        order_hash: order.actionHash,
+      }),
+    );
+
+    // Bob hands the producer over to Carol, who corrects the delivery
+    const handedOverProducer = await bob.producers.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      {
+        ...producer.entry,
+        liason: carol.player.agentPubKey,
+      },
+    );
+
+    await dhtSync(
+      [alice.player, bob.player, carol.player],
+      alice.player.cells[0].cell_id[0],
+    );
+
+    const correctedDelivery = await carol.orders.client.updateProducerDelivery(
+      producerDelivery.actionHash,
+      producerDelivery.entry,
+    );
+    assert.deepEqual(
+      correctedDelivery.entry.latest_producer_hash,
+      handedOverProducer.actionHash,
+    );
+
+    await dhtSync(
+      [alice.player, bob.player, carol.player],
+      alice.player.cells[0].cell_id[0],
+    );
+
+    // Bob can't update Carol's correction
+    await expect(
+      () =>
+        bob.orders.client.updateProducerDelivery(
+          correctedDelivery.actionHash,
+          producerDelivery.entry,
+        ),
+      "Bob was able to update a ProducerDelivery after no longer being the liason",
+    ).rejects.toThrowError(undefined);
+  });
+});
@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync } from "@holochain/tryorama";
 import {
@@ -12,13 +12,19 @@ import { EntryRecord } from "@holochain-open-dev/utils";
 import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-decoding.js";
 import { toPromise } from "@holochain-open-dev/signals";
 
-import { ProducerInvoice } from "../../../../ui/src/plenty/orders/types.js";
+import {
+  InvoiceLine,
+  ProducerInvoice,
+} from "../../../../ui/src/plenty/orders/types.js";
 import { sampleProducerInvoice } from "../../../../ui/src/plenty/orders/mocks.js";
 import {
   bookkeeperRoleConfig,
   orderManagerRoleConfig,
 } from "../../../../ui/src/roles.js";
-import { sampleProducer } from "../../../../ui/src/plenty/producers/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
 import { setup } from "../../setup.js";
 
 test("create ProducerInvoice", async () => {
@@ -192,3 +198,62 @@ test("create and delete ProducerInvoice", async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test("invoice lines with out of range quantities or VAT are rejected", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    await alice.roles.client.assignRole(bookkeeperRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const product = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+      }),
+    );
+
+    const line = (
+      quantity: number,
+      unit_price_cents: number,
+      vat_percentage: number,
+      subtotal_cents: number,
+      vat_cents: number,
+    ): InvoiceLine => ({
+      original_product_hash: product.actionHash,
+      quantity,
+      unit_price_cents,
+      vat_percentage,
+      subtotal_cents,
+      vat_cents,
+      total_cents: subtotal_cents + vat_cents,
+    });
+
+    for (const invalidLine of [
+      line(Infinity, 150, 10, 0, 0),
+      line(1e30, 4294967295, 10, 0, 0),
+      line(2, 150, 1000, 300, 3000),
+      line(2, 150, NaN, 300, 0),
+    ]) {
+      await expect(
+        alice.orders.client.createProducerInvoice(
+          await sampleProducerInvoice(alice.orders.client, producer.actionHash, {
+            lines: [invalidLine],
+          }),
+        ),
+      ).rejects.toThrowError(undefined);
+    }
+
+    const producerInvoice = await alice.orders.client.createProducerInvoice(
+      await sampleProducerInvoice(alice.orders.client, producer.actionHash, {
+        lines: [line(2, 150, 10, 300, 30)],
+      }),
+    );
+    assert.ok(producerInvoice);
+  });
+});
@@ -0,0 +1,136 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { encodeHashToBase64 } from "@holochain/client";
+
+import {
+  sampleHouseholdOrder,
+  sampleOpenOrder,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import { setup } from "../../setup.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("household orders can't exceed the maximum available of a product", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const { order, product } = await sampleOpenOrder(alice.orders.client, {
+      maximum_available: 3,
+    });
+
+    const aliceHousehold = await alice.households.client.createHousehold(
+      await sampleHousehold(alice.households.client, {
+        name: "Alice's Household",
+      }),
+    );
+    const bobHousehold = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const productOrder = (amount: number) => [
+      {
+        original_product_hash: product.actionHash,
+        ordered_product_hash: product.actionHash,
+        amount,
+      },
+    ];
+
+    // Nothing ordered yet
+    let availability = await bob.orders.client.getRemainingAvailability(
+      order.actionHash,
+    );
+    assert.equal(availability.length, 1);
+    assert.equal(
+      encodeHashToBase64(availability[0].original_product_hash),
+      encodeHashToBase64(product.actionHash),
+    );
+    assert.equal(availability[0].maximum_available, 3);
+    assert.equal(availability[0].ordered_amount, 0);
+    assert.equal(availability[0].remaining, 3);
+    assert.isFalse(availability[0].oversubscribed);
+
+    const bobHouseholdOrder = await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, bobHousehold.actionHash, {
+        order_hash: order.actionHash,
+        products: productOrder(2),
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    availability = await alice.orders.client.getRemainingAvailability(
+      order.actionHash,
+    );
+    assert.equal(availability[0].ordered_amount, 2);
+    assert.equal(availability[0].remaining, 1);
+
+    // Only 1 unit is left for Alice's household
+    await expect(
+      alice.orders.client.createHouseholdOrder(
+        await sampleHouseholdOrder(
+          alice.orders.client,
+          aliceHousehold.actionHash,
+          {
+            order_hash: order.actionHash,
+            products: productOrder(2),
+          },
+        ),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    await alice.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(alice.orders.client, aliceHousehold.actionHash, {
+        order_hash: order.actionHash,
+        products: productOrder(1),
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    availability = await bob.orders.client.getRemainingAvailability(
+      order.actionHash,
+    );
+    assert.equal(availability[0].ordered_amount, 3);
+    assert.equal(availability[0].remaining, 0);
+    assert.isFalse(availability[0].oversubscribed);
+
+    // Bob can't raise the order above what the other households left
+    await expect(
+      bob.orders.client.updateHouseholdOrder(
+        bobHouseholdOrder.actionHash,
+        bobHouseholdOrder.actionHash,
+        {
+          ...bobHouseholdOrder.entry,
+          products: productOrder(3),
+        },
+      ),
+    ).rejects.toThrowError(undefined);
+
+    // But Bob can lower it
+    await bob.orders.client.updateHouseholdOrder(
+      bobHouseholdOrder.actionHash,
+      bobHouseholdOrder.actionHash,
+      {
+        ...bobHouseholdOrder.entry,
+        products: productOrder(1),
+      },
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    availability = await alice.orders.client.getRemainingAvailability(
+      order.actionHash,
+    );
+    assert.equal(availability[0].ordered_amount, 2);
+    assert.equal(availability[0].remaining, 1);
+  });
+});
@@ -0,0 +1,352 @@
+import { assert, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { encodeHashToBase64 } from "@holochain/client";
+import { EntryRecord } from "@holochain-open-dev/utils";
+
+import {
+  InvoiceLine,
+  ProducerDelivery,
+  ProductDifference,
+} from "../../../../ui/src/plenty/orders/types.js";
+import {
+  Producer,
+  Product,
+} from "../../../../ui/src/plenty/producers/types.js";
+import {
+  sampleOpenOrderOfferingProducts,
+  sampleProducerInvoice,
+} from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import {
+  bookkeeperRoleConfig,
+  orderManagerRoleConfig,
+} from "../../../../ui/src/roles.js";
+
+test("reconcile_order compares the deliveries of each producer with their invoices", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+    await alice.roles.client.assignRole(bookkeeperRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const createProducer = async () =>
+      alice.producers.client.createProducer(
+        await sampleProducer(alice.producers.client),
+      );
+    const createProduct = async (
+      producer: EntryRecord<Producer>,
+      price_cents: number,
+      vat_percentage: number,
+    ) =>
+      alice.producers.client.createProduct(
+        await sampleProduct(alice.producers.client, {
+          producer_hash: producer.actionHash,
+          price_cents,
+          vat_percentage,
+        }),
+      );
+
+    // One producer for each status: matching, mismatching,
+    // not invoiced yet, and invoiced without a delivery
+    const matchingProducer = await createProducer();
+    const matchingProduct = await createProduct(matchingProducer, 200, 10);
+
+    const mismatchingProducer = await createProducer();
+    const shortInvoicedProduct = await createProduct(
+      mismatchingProducer,
+      300,
+      0,
+    );
+    const overpricedProduct = await createProduct(mismatchingProducer, 100, 0);
+
+    const notInvoicedProducer = await createProducer();
+    const notInvoicedProduct = await createProduct(
+      notInvoicedProducer,
+      400,
+      0,
+    );
+
+    const notDeliveredProducer = await createProducer();
+    const notDeliveredProduct = await createProduct(
+      notDeliveredProducer,
+      500,
+      0,
+    );
+
+    const order = await sampleOpenOrderOfferingProducts(alice.orders.client, [
+      matchingProduct,
+    ]);
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    const latestOrder = await bob.orders.client.getLatestOrder(
+      order.actionHash,
+    );
+    await bob.orders.client.createHouseholdOrder({
+      order_hash: order.actionHash,
+      latest_order_hash: latestOrder!.actionHash,
+      household_hash: household.actionHash,
+      products: [
+        {
+          original_product_hash: matchingProduct.actionHash,
+          ordered_product_hash: matchingProduct.actionHash,
+          amount: 2,
+        },
+      ],
+    });
+
+    // The price changes after Bob's household ordered,
+    // but the producer still bills the ordered price
+    await alice.producers.client.updateProduct(
+      matchingProduct.actionHash,
+      matchingProduct.actionHash,
+      {
+        ...matchingProduct.entry,
+        price_cents: 250,
+      },
+    );
+
+    const deliver = (
+      producer: EntryRecord<Producer>,
+      deliveries: Array<[EntryRecord<Product>, number]>,
+    ) => {
+      const products: ProducerDelivery["products"] = {};
+      for (const [product, amount] of deliveries) {
+        products[encodeHashToBase64(product.actionHash)] = {
+          type: "Delivered",
+          delivered_amount: {
+            type: "FixedAmountProduct",
+            delivered_products: [
+              {
+                amount,
+                households_hashes: [household.actionHash],
+              },
+            ],
+            price_cents_per_unit_changed: undefined,
+          },
+          comment: undefined,
+        };
+      }
+      return alice.orders.client.createProducerDelivery({
+        order_hash: order.actionHash,
+        producer_hash: producer.actionHash,
+        latest_producer_hash: producer.actionHash,
+        products,
+      });
+    };
+    const line = (
+      product: EntryRecord<Product>,
+      quantity: number,
+      unit_price_cents: number,
+      vat_percentage: number,
+    ): InvoiceLine => {
+      const subtotal_cents = Math.round(quantity * unit_price_cents);
+      const vat_cents = Math.round((subtotal_cents * vat_percentage) / 100);
+      return {
+        original_product_hash: product.actionHash,
+        quantity,
+        unit_price_cents,
+        vat_percentage,
+        subtotal_cents,
+        vat_cents,
+        total_cents: subtotal_cents + vat_cents,
+      };
+    };
+    const invoice = async (
+      producer: EntryRecord<Producer>,
+      lines: Array<InvoiceLine>,
+    ) =>
+      alice.orders.client.createProducerInvoice(
+        await sampleProducerInvoice(alice.orders.client, producer.actionHash, {
+          order_hash: order.actionHash,
+          lines,
+        }),
+      );
+
+    const matchingDelivery = await deliver(matchingProducer, [
+      [matchingProduct, 2],
+    ]);
+    const matchingInvoice = await invoice(matchingProducer, [
+      line(matchingProduct, 2, 200, 10),
+    ]);
+
+    // The producer invoices one unit less than delivered,
+    // and a higher price for the other product
+    const mismatchingDelivery = await deliver(mismatchingProducer, [
+      [shortInvoicedProduct, 3],
+      [overpricedProduct, 1],
+    ]);
+    await invoice(mismatchingProducer, [
+      line(shortInvoicedProduct, 2, 300, 0),
+      line(overpricedProduct, 1, 120, 0),
+    ]);
+
+    const notInvoicedDelivery = await deliver(notInvoicedProducer, [
+      [notInvoicedProduct, 1],
+    ]);
+
+    const notDeliveredInvoice = await invoice(notDeliveredProducer, [
+      line(notDeliveredProduct, 1, 500, 0),
+    ]);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // The drafted lines are priced like the reconciliation, with the ordered revision
+    const draftedLines = await alice.orders.client.draftProducerInvoiceLines(
+      order.actionHash,
+      matchingProducer.actionHash,
+    );
+    assert.deepEqual(draftedLines, [line(matchingProduct, 2, 200, 10)]);
+
+    const reconciliation = await alice.orders.client.reconcileOrder(
+      order.actionHash,
+    );
+    assert.deepEqual(reconciliation.order_hash, order.actionHash);
+    assert.equal(reconciliation.producers.length, 4);
+
+    const reconciliationFor = (producer: EntryRecord<Producer>) => {
+      const producerReconciliation = reconciliation.producers.find(
+        (p) =>
+          encodeHashToBase64(p.producer_hash) ===
+          encodeHashToBase64(producer.actionHash),
+      );
+      assert.ok(producerReconciliation);
+      return producerReconciliation!;
+    };
+    const differenceFor = (
+      differences: Array<ProductDifference>,
+      product: EntryRecord<Product>,
+    ) =>
+      differences.find(
+        (d) =>
+          encodeHashToBase64(d.original_product_hash) ===
+          encodeHashToBase64(product.actionHash),
+      );
+
+    // Options are decoded as null, so the differences are compared field by field
+    const assertDifference = (
+      difference: ProductDifference | undefined,
+      expected: ProductDifference,
+    ) => {
+      assert.ok(difference);
+      for (const [field, value] of Object.entries(expected)) {
+        if (field === "original_product_hash") {
+          assert.deepEqual(difference!.original_product_hash, value);
+        } else {
+          assert.equal(
+            difference![field as keyof ProductDifference],
+            value,
+            field,
+          );
+        }
+      }
+    };
+
+    const matching = reconciliationFor(matchingProducer);
+    assert.equal(matching.status.type, "Matching");
+    assert.deepEqual(
+      matching.producer_delivery_hash,
+      matchingDelivery.actionHash,
+    );
+    assert.deepEqual(matching.producer_invoices_hashes, [
+      matchingInvoice.actionHash,
+    ]);
+    assert.equal(matching.expected_total_cents, 440);
+    assert.equal(matching.invoiced_total_cents, 440);
+    assert.deepEqual(matching.differences, []);
+
+    const mismatching = reconciliationFor(mismatchingProducer);
+    assert.equal(mismatching.status.type, "Mismatch");
+    assert.deepEqual(
+      mismatching.producer_delivery_hash,
+      mismatchingDelivery.actionHash,
+    );
+    assert.equal(mismatching.expected_total_cents, 900 + 100);
+    assert.equal(mismatching.invoiced_total_cents, 600 + 120);
+    assert.equal(mismatching.differences.length, 2);
+
+    assertDifference(
+      differenceFor(mismatching.differences, shortInvoicedProduct),
+      {
+        original_product_hash: shortInvoicedProduct.actionHash,
+        delivered_quantity: 3,
+        invoiced_quantity: 2,
+        catalog_price_cents: 300,
+        delivered_price_cents: undefined,
+        invoiced_price_cents: 300,
+        expected_total_cents: 900,
+        invoiced_total_cents: 600,
+      },
+    );
+    assertDifference(
+      differenceFor(mismatching.differences, overpricedProduct),
+      {
+        original_product_hash: overpricedProduct.actionHash,
+        delivered_quantity: 1,
+        invoiced_quantity: 1,
+        catalog_price_cents: 100,
+        delivered_price_cents: undefined,
+        invoiced_price_cents: 120,
+        expected_total_cents: 100,
+        invoiced_total_cents: 120,
+      },
+    );
+
+    const notInvoiced = reconciliationFor(notInvoicedProducer);
+    assert.equal(notInvoiced.status.type, "MissingInvoice");
+    assert.deepEqual(
+      notInvoiced.producer_delivery_hash,
+      notInvoicedDelivery.actionHash,
+    );
+    assert.deepEqual(notInvoiced.producer_invoices_hashes, []);
+    assert.equal(notInvoiced.expected_total_cents, 400);
+    assert.equal(notInvoiced.invoiced_total_cents, 0);
+    assert.equal(notInvoiced.differences.length, 1);
+    assertDifference(notInvoiced.differences[0], {
+      original_product_hash: notInvoicedProduct.actionHash,
+      delivered_quantity: 1,
+      invoiced_quantity: 0,
+      catalog_price_cents: 400,
+      delivered_price_cents: undefined,
+      invoiced_price_cents: undefined,
+      expected_total_cents: 400,
+      invoiced_total_cents: 0,
+    });
+
+    const notDelivered = reconciliationFor(notDeliveredProducer);
+    assert.equal(notDelivered.status.type, "MissingDelivery");
+    assert.equal(notDelivered.producer_delivery_hash, undefined);
+    assert.deepEqual(notDelivered.producer_invoices_hashes, [
+      notDeliveredInvoice.actionHash,
+    ]);
+    assert.equal(notDelivered.expected_total_cents, 0);
+    assert.equal(notDelivered.invoiced_total_cents, 500);
+    assert.equal(notDelivered.differences.length, 1);
+    assertDifference(notDelivered.differences[0], {
+      original_product_hash: notDeliveredProduct.actionHash,
+      delivered_quantity: 0,
+      invoiced_quantity: 1,
+      catalog_price_cents: 500,
+      delivered_price_cents: undefined,
+      invoiced_price_cents: 500,
+      expected_total_cents: 0,
+      invoiced_total_cents: 500,
+    });
+  });
+});
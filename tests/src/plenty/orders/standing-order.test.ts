@@ -0,0 +1,216 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { EntryRecord } from "@holochain-open-dev/utils";
+import { cleanNodeDecoding } from "@holochain-open-dev/utils/dist/clean-node-decoding.js";
+
+import { StandingOrder } from "../../../../ui/src/plenty/orders/types.js";
+import { sampleOpenOrderOfferingProducts } from "../../../../ui/src/plenty/orders/mocks.js";
+import {
+  sampleProducer,
+  sampleProduct,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { sampleHousehold } from "../../../../ui/src/plenty/households/mocks.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("create, update and delete StandingOrder", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+    const producer = await bob.producers.client.createProducer(
+      await sampleProducer(bob.producers.client),
+    );
+    const product = await bob.producers.client.createProduct(
+      await sampleProduct(bob.producers.client, {
+        producer_hash: producer.actionHash,
+      }),
+    );
+
+    const sample: StandingOrder = {
+      household_hash: household.actionHash,
+      products: [
+        {
+          original_product_hash: product.actionHash,
+          amount: 1,
+        },
+      ],
+    };
+
+    // A product can't appear twice, nor with a zero amount
+    await expect(
+      bob.orders.client.createStandingOrder({
+        ...sample,
+        products: [...sample.products, ...sample.products],
+      }),
+    ).rejects.toThrowError(undefined);
+    await expect(
+      bob.orders.client.createStandingOrder({
+        ...sample,
+        products: [{ original_product_hash: product.actionHash, amount: 0 }],
+      }),
+    ).rejects.toThrowError(undefined);
+
+    // Bob creates the StandingOrder of Bob's household
+    const standingOrder: EntryRecord<StandingOrder> =
+      await bob.orders.client.createStandingOrder(sample);
+    assert.ok(standingOrder);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Alice is not a member of the household, so they can't create nor update its standing orders
+    await expect(
+      alice.orders.client.createStandingOrder(sample),
+    ).rejects.toThrowError(undefined);
+    await expect(
+      alice.orders.client.updateStandingOrder(
+        standingOrder.actionHash,
+        standingOrder.actionHash,
+        sample,
+      ),
+    ).rejects.toThrowError(undefined);
+
+    let links = await alice.orders.client.getStandingOrdersForHousehold(
+      household.actionHash,
+    );
+    assert.equal(links.length, 1);
+    let latest = await alice.orders.client.getLatestStandingOrder(
+      standingOrder.actionHash,
+    );
+    assert.deepEqual(sample, cleanNodeDecoding(latest!.entry));
+
+    // Bob orders more each time
+    const contentUpdate: StandingOrder = {
+      ...sample,
+      products: [{ original_product_hash: product.actionHash, amount: 2 }],
+    };
+    await bob.orders.client.updateStandingOrder(
+      standingOrder.actionHash,
+      standingOrder.actionHash,
+      contentUpdate,
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    latest = await alice.orders.client.getLatestStandingOrder(
+      standingOrder.actionHash,
+    );
+    assert.deepEqual(contentUpdate, cleanNodeDecoding(latest!.entry));
+
+    // Alice can't delete it either, but Bob can
+    await expect(
+      alice.orders.client.deleteStandingOrder(standingOrder.actionHash),
+    ).rejects.toThrowError(undefined);
+    await bob.orders.client.deleteStandingOrder(standingOrder.actionHash);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    links = await alice.orders.client.getStandingOrdersForHousehold(
+      household.actionHash,
+    );
+    assert.equal(links.length, 0);
+  });
+});
+
+test("apply_standing_order orders the offered products and skips the rest", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client),
+    );
+    const product = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        maximum_available: 10,
+      }),
+    );
+    const scarceProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        maximum_available: 3,
+      }),
+    );
+    const notOfferedProduct = await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+      }),
+    );
+    const order = await sampleOpenOrderOfferingProducts(alice.orders.client, [
+      product,
+      scarceProduct,
+    ]);
+
+    // The product changed since it was added to the standing order
+    const updatedProduct = await alice.producers.client.updateProduct(
+      product.actionHash,
+      product.actionHash,
+      {
+        ...product.entry,
+        price_cents: product.entry.price_cents + 1,
+      },
+    );
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client, {
+        name: "Bob's Household",
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Without a standing order there is nothing to apply
+    await expect(
+      bob.orders.client.applyStandingOrder(order.actionHash),
+    ).rejects.toThrowError(undefined);
+
+    await bob.orders.client.createStandingOrder({
+      household_hash: household.actionHash,
+      products: [
+        { original_product_hash: product.actionHash, amount: 2 },
+        { original_product_hash: scarceProduct.actionHash, amount: 5 },
+        { original_product_hash: notOfferedProduct.actionHash, amount: 1 },
+      ],
+    });
+
+    const { householdOrder, skippedLines } =
+      await bob.orders.client.applyStandingOrder(order.actionHash);
+
+    // Only the offered product with enough units left is ordered, in its latest revision
+    assert.ok(householdOrder);
+    assert.deepEqual(householdOrder!.entry.household_hash, household.actionHash);
+    assert.deepEqual(householdOrder!.entry.products, [
+      {
+        original_product_hash: product.actionHash,
+        ordered_product_hash: updatedProduct.actionHash,
+        amount: 2,
+      },
+    ]);
+
+    assert.equal(skippedLines.length, 2);
+    assert.deepEqual(skippedLines[0].original_product_hash, scarceProduct.actionHash);
+    assert.deepEqual(skippedLines[0].reason, {
+      type: "NotEnoughAvailable",
+      remaining: 3,
+    });
+    assert.deepEqual(
+      skippedLines[1].original_product_hash,
+      notOfferedProduct.actionHash,
+    );
+    assert.deepEqual(skippedLines[1].reason, { type: "NotOffered" });
+
+    // The standing order can only be applied once per order
+    await expect(
+      bob.orders.client.applyStandingOrder(order.actionHash),
+    ).rejects.toThrowError(undefined);
+  });
+});
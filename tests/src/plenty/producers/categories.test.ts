@@ -0,0 +1,165 @@
+import { assert, expect, test } from "vitest";
+
+import { runScenario, dhtSync } from "@holochain/tryorama";
+import { EntryRecord } from "@holochain-open-dev/utils";
+
+import { Product } from "../../../../ui/src/plenty/producers/types.js";
+import {
+  sampleProduct,
+  sampleProducer,
+} from "../../../../ui/src/plenty/producers/mocks.js";
+import { setup } from "../../setup.js";
+import { orderManagerRoleConfig } from "../../../../ui/src/roles.js";
+
+test("nested categories follow product updates and deletes", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    const product: EntryRecord<Product> =
+      await alice.producers.client.createProduct(
+        await sampleProduct(alice.producers.client, {
+          categories: ["Vegetables / Roots"],
+        }),
+      );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    assert.deepEqual(await bob.producers.client.getAllCategories(), [
+      "Vegetables",
+      "Vegetables/Roots",
+    ]);
+    assert.deepEqual(await bob.producers.client.getSubcategories(""), [
+      "Vegetables",
+    ]);
+    assert.deepEqual(
+      await bob.producers.client.getSubcategories("Vegetables"),
+      ["Vegetables/Roots"],
+    );
+
+    const updatedProduct = await alice.producers.client.updateProduct(
+      product.actionHash,
+      product.actionHash,
+      {
+        ...product.entry,
+        categories: ["Fruits"],
+      },
+    );
+    assert.ok(updatedProduct);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // The product is no longer linked from its old category, which disappears
+    assert.equal(
+      (await bob.producers.client.getProductsForCategory("Vegetables/Roots"))
+        .length,
+      0,
+    );
+    assert.equal(
+      (await bob.producers.client.getProductsForCategory("Fruits")).length,
+      1,
+    );
+    assert.deepEqual(await bob.producers.client.getAllCategories(), [
+      "Fruits",
+    ]);
+
+    await alice.producers.client.deleteProduct(product.actionHash);
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    assert.equal(
+      (await bob.producers.client.getProductsForCategory("Fruits")).length,
+      0,
+    );
+    assert.deepEqual(await bob.producers.client.getAllCategories(), []);
+  });
+});
+
+test("only order managers can rename and merge categories", async () => {
+  await runScenario(async (scenario) => {
+    const { alice, bob } = await setup(scenario);
+
+    const producer = await alice.producers.client.createProducer(
+      await sampleProducer(alice.producers.client, {
+        editors: { type: "AllMembers" },
+      }),
+    );
+
+    await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        categories: ["Vegetables/Roots"],
+      }),
+    );
+    await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        producer_hash: producer.actionHash,
+        categories: ["Fruits"],
+      }),
+    );
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    // Bob can edit the products, but is not an order manager
+    await expect(
+      bob.producers.client.renameCategory("Vegetables", "Greens"),
+    ).rejects.toThrowError(undefined);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    await alice.producers.client.renameCategory("Vegetables", "Greens");
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    assert.deepEqual(await bob.producers.client.getSubcategories(""), [
+      "Fruits",
+      "Greens",
+    ]);
+    assert.equal(
+      (await bob.producers.client.getProductsForCategory("Greens/Roots"))
+        .length,
+      1,
+    );
+
+    await alice.producers.client.mergeCategories(["Fruits"], "Greens");
+
+    await dhtSync([alice.player, bob.player], alice.player.cells[0].cell_id[0]);
+
+    assert.deepEqual(await bob.producers.client.getAllCategories(), [
+      "Greens",
+      "Greens/Roots",
+    ]);
+    assert.equal(
+      (await bob.producers.client.getProductsForCategory("Greens")).length,
+      1,
+    );
+  });
+});
+
+test("categories can't be nested too deep", async () => {
+  await runScenario(async (scenario) => {
+    const { alice } = await setup(scenario);
+
+    await alice.producers.client.createProduct(
+      await sampleProduct(alice.producers.client, {
+        categories: ["Food/Vegetables/Roots/Carrots"],
+      }),
+    );
+
+    await expect(
+      alice.producers.client.createProduct(
+        await sampleProduct(alice.producers.client, {
+          categories: ["Food/Vegetables/Roots/Carrots/Orange"],
+        }),
+      ),
+    ).rejects.toThrowError(undefined);
+
+    assert.deepEqual(await alice.producers.client.getAllCategories(), [
+      "Food",
+      "Food/Vegetables",
+      "Food/Vegetables/Roots",
+      "Food/Vegetables/Roots/Carrots",
+    ]);
+  });
+});
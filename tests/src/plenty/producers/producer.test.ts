@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync } from '@holochain/tryorama';
 import { ActionHash, SignedActionHashed, Delete, Record } from '@holochain/client';
@@ -110,3 +110,78 @@ test('create and delete Producer', async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test('only the editors of a Producer can update it', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    // Alice creates a Producer that only its liason can edit
+    const producer: EntryRecord<Producer> = await alice.store.client.createProducer(await sampleProducer(alice.store.client));
+    assert.ok(producer);
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // Bob is not the liason, so they can't update it
+    await expect(
+      bob.store.client.updateProducer(producer.actionHash, producer.actionHash, await sampleProducer(alice.store.client))
+    ).rejects.toThrowError(undefined);
+
+    // Alice lets all members edit the Producer
+    const updatedProducer: EntryRecord<Producer> = await alice.store.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      await sampleProducer(alice.store.client, { editors: { type: "AllMembers" } })
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // Now Bob can update it
+    const bobsUpdate: EntryRecord<Producer> = await bob.store.client.updateProducer(
+      producer.actionHash,
+      updatedProducer.actionHash,
+      await sampleProducer(alice.store.client, { editors: { type: "AllMembers" } })
+    );
+    assert.ok(bobsUpdate);
+  });
+});
+
+test('Producers can only be updated from their latest revision', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const producer: EntryRecord<Producer> = await alice.store.client.createProducer(
+      await sampleProducer(alice.store.client, { editors: { type: "AllMembers" } })
+    );
+
+    // Alice restricts the Producer to its liason
+    const restrictedProducer: EntryRecord<Producer> = await alice.store.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      { ...producer.entry, editors: { type: "Liason" } }
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // Bob can't update the older revision that let all members edit it
+    await expect(
+      bob.store.client.updateProducer(producer.actionHash, producer.actionHash, producer.entry)
+    ).rejects.toThrowError(undefined);
+
+    // Nor the latest one
+    await expect(
+      bob.store.client.updateProducer(producer.actionHash, restrictedProducer.actionHash, producer.entry)
+    ).rejects.toThrowError(undefined);
+
+    const latestProducer: EntryRecord<Producer> = await toPromise(bob.store.producers.get(producer.actionHash).latestVersion);
+    assert.deepEqual(latestProducer.actionHash, restrictedProducer.actionHash);
+  });
+});
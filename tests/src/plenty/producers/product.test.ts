@@ -1,4 +1,4 @@
-import { assert, test } from "vitest";
+import { assert, expect, test } from "vitest";
 
 import { runScenario, dhtSync } from '@holochain/tryorama';
 import { ActionHash, SignedActionHashed, Delete, Record } from '@holochain/client';
@@ -7,9 +7,13 @@ import { EntryRecord } from '@holochain-open-dev/utils';
 import { cleanNodeDecoding } from '@holochain-open-dev/utils/dist/clean-node-decoding.js';
 import { toPromise } from '@holochain-open-dev/signals';
 
-import { Product } from '../../../../ui/src/plenty/producers/types.js';
-import { sampleProduct } from '../../../../ui/src/plenty/producers/mocks.js';
+import { Producer, Product } from '../../../../ui/src/plenty/producers/types.js';
+import { sampleProduct, sampleProducer } from '../../../../ui/src/plenty/producers/mocks.js';
 import { setup } from './setup.js';
+import { setup as setupPlenty } from '../../setup.js';
+import { sampleHouseholdOrder, sampleOpenOrder, sampleOpenOrderOffering } from '../../../../ui/src/plenty/orders/mocks.js';
+import { sampleHousehold } from '../../../../ui/src/plenty/households/mocks.js';
+import { orderManagerRoleConfig } from '../../../../ui/src/roles.js';
 
 test('create Product', async () => {
   await runScenario(async scenario => {
@@ -110,3 +114,289 @@ test('create and delete Product', async () => {
     assert.equal(deletes.length, 1);
   });
 });
+
+test('only the editors of the Producer can update or delete its Products', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    // Alice creates a Product for a Producer that only its liason can edit
+    const product: EntryRecord<Product> = await alice.store.client.createProduct(await sampleProduct(alice.store.client));
+    assert.ok(product);
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // Bob tries to change its price
+    await expect(
+      bob.store.client.updateProduct(product.actionHash, product.actionHash, {
+        ...product.entry,
+        price_cents: 1,
+      })
+    ).rejects.toThrowError(undefined);
+
+    // Bob tries to delete it
+    await expect(
+      bob.store.client.deleteProduct(product.actionHash)
+    ).rejects.toThrowError(undefined);
+  });
+});
+
+test('extending the editors of a Producer lets them edit its Products', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const producer: EntryRecord<Producer> = await alice.store.client.createProducer(await sampleProducer(alice.store.client));
+    const product: EntryRecord<Product> = await alice.store.client.createProduct(
+      await sampleProduct(alice.store.client, { producer_hash: producer.actionHash })
+    );
+
+    // Alice lets all members edit the Producer
+    const updatedProducer: EntryRecord<Producer> = await alice.store.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      { ...producer.entry, editors: { type: "AllMembers" } }
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // The product was created before, but Bob can now change its price
+    const repricedProduct: EntryRecord<Product> = await bob.store.client.updateProduct(product.actionHash, product.actionHash, {
+      ...product.entry,
+      price_cents: 1,
+    });
+    assert.deepEqual(repricedProduct.entry.latest_producer_hash, updatedProducer.actionHash);
+  });
+});
+
+test('restricting the editors of a Producer stops the old editors from editing its Products', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const producer: EntryRecord<Producer> = await alice.store.client.createProducer(
+      await sampleProducer(alice.store.client, { editors: { type: "AllMembers" } })
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    // Bob can create products while all members are editors
+    const product: EntryRecord<Product> = await bob.store.client.createProduct(
+      await sampleProduct(bob.store.client, { producer_hash: producer.actionHash })
+    );
+
+    // Alice restricts the Producer back to its liason
+    await alice.store.client.updateProducer(
+      producer.actionHash,
+      producer.actionHash,
+      { ...producer.entry, editors: { type: "Liason" } }
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    await expect(
+      bob.store.client.updateProduct(product.actionHash, product.actionHash, {
+        ...product.entry,
+        price_cents: 1,
+      })
+    ).rejects.toThrowError(undefined);
+
+    await expect(
+      bob.store.client.createProduct(
+        await sampleProduct(bob.store.client, { producer_hash: producer.actionHash })
+      )
+    ).rejects.toThrowError(undefined);
+
+    // Alice, the liason, still can
+    const repricedProduct = await alice.store.client.updateProduct(product.actionHash, product.actionHash, {
+      ...product.entry,
+      price_cents: 1,
+    });
+    assert.ok(repricedProduct);
+  });
+});
+
+test('get price history of a Product', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const sample = await sampleProduct(alice.store.client);
+    const product: EntryRecord<Product> = await alice.store.client.createProduct(sample);
+
+    // Alice renames the Product, which doesn't change its price
+    const renamedProduct: EntryRecord<Product> = await alice.store.client.updateProduct(product.actionHash, product.actionHash, {
+      ...sample,
+      name: "Renamed",
+    });
+
+    // Alice raises its price
+    const repricedProduct: EntryRecord<Product> = await alice.store.client.updateProduct(product.actionHash, renamedProduct.actionHash, {
+      ...sample,
+      name: "Renamed",
+      price_cents: sample.price_cents + 100,
+    });
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    const priceHistory = await bob.store.client.getPriceHistory(product.actionHash);
+    assert.equal(priceHistory.length, 2);
+    assert.deepEqual(priceHistory[0].product_hash, product.actionHash);
+    assert.equal(priceHistory[0].price_cents, sample.price_cents);
+    assert.deepEqual(priceHistory[1].product_hash, repricedProduct.actionHash);
+    assert.equal(priceHistory[1].price_cents, sample.price_cents + 100);
+    assert.deepEqual(priceHistory[1].author, alice.player.agentPubKey);
+    assert.equal(priceHistory[1].orders_hashes.length, 0);
+  });
+});
+
+test('get the orders in which each price of a Product was ordered', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setupPlenty(scenario);
+
+    await alice.roles.client.assignRole(orderManagerRoleConfig.role, [
+      alice.player.agentPubKey,
+    ]);
+
+    // The product is created before the order that offers it
+    const { order: firstOrder, product } = await sampleOpenOrder(alice.orders.client);
+
+    const household = await bob.households.client.createHousehold(
+      await sampleHousehold(bob.households.client),
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: firstOrder.actionHash,
+        products: [{
+          original_product_hash: product.actionHash,
+          ordered_product_hash: product.actionHash,
+          amount: 1,
+        }],
+      }),
+    );
+
+    // Alice raises the price and offers the product again in a new order
+    const repricedProduct: EntryRecord<Product> = await alice.producers.client.updateProduct(product.actionHash, product.actionHash, {
+      ...product.entry,
+      price_cents: product.entry.price_cents + 100,
+    });
+    const secondOrder = await sampleOpenOrderOffering(alice.orders.client, product);
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    await bob.orders.client.createHouseholdOrder(
+      await sampleHouseholdOrder(bob.orders.client, household.actionHash, {
+        order_hash: secondOrder.actionHash,
+        products: [{
+          original_product_hash: product.actionHash,
+          ordered_product_hash: repricedProduct.actionHash,
+          amount: 1,
+        }],
+      }),
+    );
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    const priceHistory = await alice.producers.client.getPriceHistory(product.actionHash);
+    assert.equal(priceHistory.length, 2);
+    assert.deepEqual(priceHistory[0].orders_hashes, [firstOrder.actionHash]);
+    assert.deepEqual(priceHistory[1].orders_hashes, [secondOrder.actionHash]);
+  });
+});
+
+test('import Products matching them by product ID', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const producer = await alice.store.client.createProducer(await sampleProducer(alice.store.client));
+    const rice = await sampleProduct(alice.store.client, { producer_hash: producer.actionHash, product_id: "1", name: "Rice" });
+    const beans = await sampleProduct(alice.store.client, { producer_hash: producer.actionHash, product_id: "2", name: "Beans" });
+
+    const firstImport = await alice.store.client.importProducts(producer.actionHash, [rice, beans]);
+    assert.equal(firstImport.created.length, 2);
+
+    // Alice imports the catalog again, with a new price for the beans and a new product
+    const lentils = await sampleProduct(alice.store.client, { producer_hash: producer.actionHash, product_id: "3", name: "Lentils" });
+    const secondImport = await alice.store.client.importProducts(producer.actionHash, [
+      rice,
+      { ...beans, price_cents: beans.price_cents + 50 },
+      lentils,
+    ]);
+    assert.equal(secondImport.created.length, 1);
+    assert.equal(secondImport.updated.length, 1);
+    assert.equal(secondImport.unchanged.length, 1);
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    const links = await bob.store.client.getProductsForProducer(producer.actionHash);
+    assert.equal(links.length, 3);
+  });
+});
+
+test('search Products by name', async () => {
+  await runScenario(async scenario => {
+    const { alice, bob } = await setup(scenario);
+
+    const brownRice: EntryRecord<Product> = await alice.store.client.createProduct(await sampleProduct(alice.store.client, { name: "Organic Brown Rice" }));
+    const whiteRice: EntryRecord<Product> = await alice.store.client.createProduct(await sampleProduct(alice.store.client, { name: "White rice" }));
+    const beans: EntryRecord<Product> = await alice.store.client.createProduct(await sampleProduct(alice.store.client, { name: "Black beans" }));
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    let results = await bob.store.client.searchProducts("ric");
+    assert.equal(results.length, 2);
+
+    results = await bob.store.client.searchProducts("BROWN rice");
+    assert.equal(results.length, 1);
+    assert.deepEqual(results[0].actionHash, brownRice.actionHash);
+
+    // Alice renames the beans, and the search returns the latest revision
+    const renamedBeans = await alice.store.client.updateProduct(beans.actionHash, beans.actionHash, {
+      ...beans.entry,
+      name: "Rice beans",
+    });
+    // Alice deletes the white rice
+    await alice.store.client.deleteProduct(whiteRice.actionHash);
+
+    await dhtSync(
+      [alice.player, bob.player],
+      alice.player.cells[0].cell_id[0]
+    );
+
+    results = await bob.store.client.searchProducts("black");
+    assert.equal(results.length, 0);
+
+    results = await bob.store.client.searchProducts("rice");
+    assert.equal(results.length, 2);
+    assert.ok(results.find(r => r.actionHash.toString() === renamedBeans.actionHash.toString()));
+  });
+});
@@ -32,6 +32,7 @@ import {
 
 import { OrdersStore } from "../../ui/src/plenty/orders/orders-store.js";
 import { OrdersClient } from "../../ui/src/plenty/orders/orders-client.js";
+import { ClubSettings } from "../../ui/src/plenty/orders/types.js";
 import { rolesConfig } from "../../ui/src/roles.js";
 import { HouseholdsStore } from "../../ui/src/plenty/households/households-store.js";
 import { HouseholdsClient } from "../../ui/src/plenty/households/households-client.js";
@@ -85,8 +86,8 @@ function setupStores(appClient: AppClient) {
   };
 }
 
-export async function setup(scenario: Scenario) {
-  const [alice, bob] = await setupPlayers(scenario);
+export async function setup(scenario: Scenario, clubSettings?: ClubSettings) {
+  const [alice, bob] = await setupPlayers(scenario, 2, clubSettings);
 
   patchCallZome(alice.appWs as any);
   patchCallZome(bob.appWs as any);
@@ -107,6 +108,31 @@ export async function setup(scenario: Scenario) {
   };
 }
 
+export async function setupWithThreePlayers(scenario: Scenario) {
+  const [alice, bob, carol] = await setupPlayers(scenario, 3);
+
+  patchCallZome(alice.appWs as any);
+  patchCallZome(bob.appWs as any);
+  patchCallZome(carol.appWs as any);
+
+  await scenario.shareAllAgents();
+
+  return {
+    alice: {
+      player: alice,
+      ...setupStores(alice.appWs as AppClient),
+    },
+    bob: {
+      player: bob,
+      ...setupStores(bob.appWs as AppClient),
+    },
+    carol: {
+      player: carol,
+      ...setupStores(carol.appWs as AppClient),
+    },
+  };
+}
+
 export function patchCallZome(appWs: AppWebsocket) {
   const callZome = appWs.callZome;
 
@@ -128,6 +154,7 @@ export function patchCallZome(appWs: AppWebsocket) {
 export async function setupPlayers(
   scenario: Scenario,
   playerNumber: number = 2,
+  clubSettings?: ClubSettings,
 ) {
   const progenitorConductor = await scenario.addConductor();
 
@@ -140,6 +167,7 @@ export async function setupPlayers(
     ...role.dna.modifiers,
     properties: {
       progenitors: [encodeHashToBase64(alicePubKey)],
+      ...(clubSettings ? { club_settings: clubSettings } : {}),
     } as any,
   };
   const appBundleSource = { bundle: appBundle };
@@ -128,8 +128,12 @@ export class HomePage extends SignalWatcher(LitElement) {
                 ] as ProvisionedCell;
                 const props: any = decode(cell.dna_modifiers.properties);
                 const progenitor = props.progenitors[0];
+                // The club settings are part of the DNA properties, so joiners need them too
+                const query = props.club_settings
+                  ? `?currency=${encodeURIComponent(props.club_settings.currency_code)}&decimals=${props.club_settings.decimal_places}`
+                  : "";
 
-                await writeText(`plenty://${progenitor}`);
+                await writeText(`plenty://${progenitor}${query}`);
                 notify(
                   msg(
                     "Invite link copied! Send it to the person you want to invite to this buyers club.",
@@ -14,6 +14,8 @@ import {
   slice,
 } from "@holochain-open-dev/utils";
 import { msg, str } from "@lit/localize";
+import { notify, notifyError } from "@holochain-open-dev/elements";
+import { core } from "@tauri-apps/api";
 
 import "@shoelace-style/shoelace/dist/components/icon-button/icon-button.js";
 import "@shoelace-style/shoelace/dist/components/card/card.js";
@@ -86,6 +88,44 @@ export class ClosedOrderDetail extends SignalWatcher(LitElement) {
   @state()
   creatingProducerDelivery = false;
 
+  @state()
+  exportingPackingList = false;
+
+  async exportPackingList(format: "csv" | "html") {
+    if (this.exportingPackingList) return;
+    this.exportingPackingList = true;
+    try {
+      const packingList = await this.ordersStore.client.getPackingList(
+        this.orderHash,
+      );
+      const path: string = await core.invoke("export_packing_list", {
+        orderName: this.order.entry.name,
+        packingList,
+        format,
+      });
+      notify(msg(str`Packing list saved to ${path}.`));
+    } catch (e) {
+      notifyError(msg("Error exporting the packing list."));
+      console.error(e);
+    }
+    this.exportingPackingList = false;
+  }
+
+  renderExportPackingList() {
+    return html`<div class="row" style="gap: 8px; justify-content: end">
+      <sl-button
+        .loading=${this.exportingPackingList}
+        @click=${() => this.exportPackingList("csv")}
+        >${msg("Export packing list (CSV)")}</sl-button
+      >
+      <sl-button
+        .loading=${this.exportingPackingList}
+        @click=${() => this.exportPackingList("html")}
+        >${msg("Export packing list (HTML)")}</sl-button
+      >
+    </div>`;
+  }
+
   renderProducerTab(
     producerHash: ActionHash,
     producer: EntryRecord<Producer>,
@@ -353,13 +393,16 @@ export class ClosedOrderDetail extends SignalWatcher(LitElement) {
           .error=${details.error}
         ></display-error>`;
       case "completed":
-        return this.renderProducersTabs(
-          details.value.producers,
-          details.value.products,
-          details.value.households,
-          details.value.householdOrders,
-          details.value.producerDeliveries,
-        );
+        return html`<div class="column" style="flex: 1; gap: 8px">
+          ${this.renderExportPackingList()}
+          ${this.renderProducersTabs(
+            details.value.producers,
+            details.value.products,
+            details.value.households,
+            details.value.householdOrders,
+            details.value.producerDeliveries,
+          )}
+        </div>`;
     }
   }
   static styles = [
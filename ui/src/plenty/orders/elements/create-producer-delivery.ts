@@ -161,6 +161,7 @@ export class CreateProducerDelivery extends SignalWatcher(LitElement) {
         await this.ordersStore.client.createProducerDelivery({
           order_hash: this.orderHash,
           producer_hash: this.producerHash,
+          latest_producer_hash: this.producerHash,
           products,
         });
       this.dispatchEvent(
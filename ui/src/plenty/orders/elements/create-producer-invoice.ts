@@ -82,6 +82,7 @@ export class CreateProducerInvoice extends SignalWatcher(LitElement) {
       order_hash: this.orderHash!,
       producer_hash: this.producerHash!,
       invoice: fields.invoice!,
+      lines: [],
     };
 
     try {
@@ -68,6 +68,7 @@ export class EditHouseholdOrder extends SignalWatcher(LitElement) {
   ) {
     const householdOrder: HouseholdOrder = {
       order_hash: currentRecord.entry.order_hash!,
+      latest_order_hash: currentRecord.entry.latest_order_hash!,
       household_hash: currentRecord.entry.household_hash!,
       products: currentRecord.entry.products!,
     };
@@ -69,6 +69,7 @@ export class EditProducerDelivery extends SignalWatcher(LitElement) {
     const producerDelivery: ProducerDelivery = {
       order_hash: currentRecord.entry.order_hash!,
       producer_hash: currentRecord.entry.producer_hash!,
+      latest_producer_hash: currentRecord.entry.latest_producer_hash!,
       products: currentRecord.entry.products!,
     };
 
@@ -71,6 +71,7 @@ export class EditProducerInvoice extends SignalWatcher(LitElement) {
       order_hash: currentRecord.entry.order_hash!,
       producer_hash: currentRecord.entry.producer_hash!,
       invoice: fields.invoice!,
+      lines: currentRecord.entry.lines ?? [],
     };
 
     try {
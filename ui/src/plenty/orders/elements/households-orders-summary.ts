@@ -25,7 +25,7 @@ import "@vaadin/grid/vaadin-grid-sort-column.js";
 import { appStyles } from "../../../app-styles.js";
 import { ordersStoreContext } from "../context.js";
 import { OrdersStore } from "../orders-store.js";
-import { HouseholdOrder, Order, OrderStatus, ProductOrder } from "../types.js";
+import { HouseholdOrder, Order, ProductOrder } from "../types.js";
 import { Household } from "../../households/types.js";
 import { HouseholdsStore } from "../../households/households-store.js";
 import { householdsStoreContext } from "../../households/context.js";
@@ -193,27 +193,7 @@ export class HouseholdsOrdersSummary extends SignalWatcher(LitElement) {
                 if (this.closingOrder) return;
                 this.closingOrder = true;
                 try {
-                  let status: OrderStatus = {
-                    type: "Closed",
-                    household_orders: Array.from(householdOrders.keys()),
-                  };
-
-                  if (status.household_orders.length === 0) {
-                    status = {
-                      type: "Finished",
-                      household_payments: [],
-                      producers_invoices: [],
-                    };
-                  }
-
-                  await this.ordersStore.client.updateOrder(
-                    this.orderHash,
-                    order.actionHash,
-                    {
-                      name: order.entry.name,
-                      status,
-                    },
-                  );
+                  await this.ordersStore.client.closeOrder(this.orderHash);
                 } catch (e) {
                   console.error(e);
                   notifyError(msg("Error closing the order."));
@@ -196,6 +196,7 @@ export class MyHouseholdOrder extends SignalWatcher(LitElement) {
         const householdOrder: HouseholdOrder = {
           household_hash: householdHash,
           order_hash: this.orderHash,
+          latest_order_hash: this.orderHash,
           products,
         };
         const record: EntryRecord<HouseholdOrder> =
@@ -213,6 +214,7 @@ export class MyHouseholdOrder extends SignalWatcher(LitElement) {
       } else {
         const householdOrder: HouseholdOrder = {
           order_hash: this.orderHash!,
+          latest_order_hash: this.orderHash!,
           household_hash: householdHash!,
           products,
         };
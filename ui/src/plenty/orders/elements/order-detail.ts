@@ -122,7 +122,8 @@ export class OrderDetail extends SignalWatcher(LitElement) {
           status: {
             type: "Open",
             available_products,
-            deadline: Date.now(), // TODO
+            // TODO: let the order manager pick the deadline
+            deadline: (Date.now() + 7 * 24 * 60 * 60 * 1000) * 1000, // In microseconds
           },
         },
       );
@@ -193,8 +194,8 @@ export class OrderDetail extends SignalWatcher(LitElement) {
       value: {
         canOrderBeOpen,
         availableProducts: Array.from(
-          availableProductsLatestVersion.value.keys(),
-        ),
+          availableProductsLatestVersion.value.values(),
+        ).map((availableProducts) => availableProducts.actionHash),
       },
     };
   }
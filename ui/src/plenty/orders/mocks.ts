@@ -4,7 +4,7 @@ import { ProducerInvoice, ProductDelivery } from "./types.js";
 
 import { ProducerDelivery } from "./types.js";
 
-import { HouseholdOrder } from "./types.js";
+import { HouseholdOrder, HouseholdPayment, HouseholdPickup } from "./types.js";
 
 import { Order } from "./types.js";
 
@@ -23,6 +23,7 @@ import {
   HoloHashMap,
   HashType,
   hash,
+  EntryRecord,
 } from "@holochain-open-dev/utils";
 import {
   decodeHashFromBase64,
@@ -42,6 +43,9 @@ import {
   encodeHashToBase64,
 } from "@holochain/client";
 import { OrdersClient } from "./orders-client.js";
+import { ProducersClient } from "../producers/producers-client.js";
+import { sampleProducer, sampleProduct } from "../producers/mocks.js";
+import { Product } from "../producers/types.js";
 
 export class OrdersZomeMock extends ZomeMock implements AppClient {
   constructor(myPubKey?: AgentPubKey) {
@@ -735,28 +739,160 @@ export async function sampleOrder(
   };
 }
 
+// Creates an Open order that offers one new product, as only order managers can do
+export async function sampleOpenOrder(
+  client: OrdersClient,
+  partialProduct: Partial<Product> = {},
+  openForMs: number = 60 * 60 * 1000,
+): Promise<{ order: EntryRecord<Order>; product: EntryRecord<Product> }> {
+  const producersClient = new ProducersClient(client.client, client.roleName);
+  const producer = await producersClient.createProducer(
+    await sampleProducer(producersClient),
+  );
+  const product = await producersClient.createProduct(
+    await sampleProduct(producersClient, {
+      producer_hash: producer.actionHash,
+      ...partialProduct,
+    }),
+  );
+
+  const order = await sampleOpenOrderOffering(client, product, openForMs);
+
+  return { order, product };
+}
+
+// An order open for an hour by default, in which only the given product is offered
+export async function sampleOpenOrderOffering(
+  client: OrdersClient,
+  product: EntryRecord<Product>,
+  openForMs: number = 60 * 60 * 1000,
+): Promise<EntryRecord<Order>> {
+  return sampleOpenOrderOfferingProducts(client, [product], openForMs);
+}
+
+// An order open for an hour by default, in which only the given products
+// from the same producer are offered
+export async function sampleOpenOrderOfferingProducts(
+  client: OrdersClient,
+  products: Array<EntryRecord<Product>>,
+  openForMs: number = 60 * 60 * 1000,
+): Promise<EntryRecord<Order>> {
+  const order = await client.createOrder(await sampleOrder(client));
+  const availableProducts = await client.createAvailableProducts(
+    await sampleAvailableProducts(
+      client,
+      products[0].entry.producer_hash,
+      products[0].entry.latest_producer_hash,
+      {
+        order_hash: order.actionHash,
+        producer_availability: {
+          type: "Available",
+          available_products: products.map((product) => product.actionHash),
+        },
+      },
+    ),
+  );
+  await client.updateOrder(order.actionHash, order.actionHash, {
+    ...order.entry,
+    status: {
+      type: "Open",
+      deadline: (Date.now() + openForMs) * 1000,
+      available_products: [availableProducts.actionHash],
+    },
+  });
+
+  return order;
+}
+
+// The first product offered in the latest Open revision of the order, if any
+async function firstOfferedProduct(
+  client: OrdersClient,
+  order: Order,
+): Promise<ActionHash | undefined> {
+  if (order.status.type !== "Open") return undefined;
+  for (const availableProductsHash of order.status.available_products) {
+    const availableProducts = await client.getOriginalAvailableProducts(
+      availableProductsHash,
+    );
+    const availability = availableProducts?.entry.producer_availability;
+    if (
+      availability?.type === "Available" &&
+      availability.available_products.length > 0
+    ) {
+      return availability.available_products[0];
+    }
+  }
+  return undefined;
+}
+
 export async function sampleHouseholdOrder(
   client: OrdersClient,
   household_hash: ActionHash,
   partialHouseholdOrder: Partial<HouseholdOrder> = {},
 ): Promise<HouseholdOrder> {
-  const productHash = await fakeActionHash();
+  const order_hash =
+    partialHouseholdOrder.order_hash ||
+    (await sampleOpenOrder(client)).order.actionHash;
+  const latestOrder = await client.getLatestOrder(order_hash);
+  const productHash = latestOrder
+    ? await firstOfferedProduct(client, latestOrder.entry)
+    : undefined;
 
+  return {
+    ...{
+      order_hash,
+      latest_order_hash: latestOrder ? latestOrder.actionHash : order_hash,
+      household_hash,
+      products: productHash
+        ? [
+            {
+              amount: 3,
+              ordered_product_hash: productHash,
+              original_product_hash: productHash,
+            },
+          ]
+        : [],
+    },
+    ...partialHouseholdOrder,
+  };
+}
+
+export async function sampleHouseholdPickup(
+  client: OrdersClient,
+  household_hash: ActionHash,
+  partialHouseholdPickup: Partial<HouseholdPickup> = {},
+): Promise<HouseholdPickup> {
   return {
     ...{
       order_hash:
-        partialHouseholdOrder.order_hash ||
+        partialHouseholdPickup.order_hash ||
         (await client.createOrder(await sampleOrder(client))).actionHash,
       household_hash,
-      products: [
-        {
-          amount: 3,
-          ordered_product_hash: productHash,
-          original_product_hash: productHash,
-        },
-      ],
+      picked_up_at: Date.now() * 1000,
+      handed_out_by: client.client.myPubKey,
+      exceptions: [],
     },
-    ...partialHouseholdOrder,
+    ...partialHouseholdPickup,
+  };
+}
+
+export async function sampleHouseholdPayment(
+  client: OrdersClient,
+  household_hash: ActionHash,
+  partialHouseholdPayment: Partial<HouseholdPayment> = {},
+): Promise<HouseholdPayment> {
+  return {
+    ...{
+      order_hash:
+        partialHouseholdPayment.order_hash ||
+        (await client.createOrder(await sampleOrder(client))).actionHash,
+      household_hash,
+      amount_cents: 1250,
+      payment_method: {
+        type: "Cash",
+      },
+    },
+    ...partialHouseholdPayment,
   };
 }
 
@@ -772,6 +908,7 @@ export async function sampleProducerDelivery(
         partialProducerDelivery.order_hash ||
         (await client.createOrder(await sampleOrder(client))).actionHash,
       producer_hash,
+      latest_producer_hash: producer_hash,
       products: {
         [encodeHashToBase64(productHash)]: {
           type: "Delivered",
@@ -805,6 +942,7 @@ export async function sampleProducerInvoice(
         (await client.createOrder(await sampleOrder(client))).actionHash,
       producer_hash,
       invoice: await fakeEntryHash(),
+      lines: [],
     },
     ...partialProducerInvoice,
   };
@@ -1,13 +1,26 @@
 import { AvailableProducts } from "./types.js";
 
-import { ProducerInvoice } from "./types.js";
+import {
+  HouseholdBill,
+  InvoiceLine,
+  OrderBill,
+  OrderReconciliation,
+  PackingList,
+  ProducerInvoice,
+} from "./types.js";
+
+import { DeliveryInconsistency, ProducerDelivery } from "./types.js";
 
-import { ProducerDelivery } from "./types.js";
+import { HouseholdOrder, ProductAvailability } from "./types.js";
 
-import { HouseholdOrder } from "./types.js";
+import { StandingOrder, SkippedStandingOrderLine } from "./types.js";
+
+import { HouseholdPayment, HouseholdPickup } from "./types.js";
 
 import { Order } from "./types.js";
 
+import { ClubSettings, ProductRevisionOrders } from "./types.js";
+
 import {
   SignedActionHashed,
   CreateLink,
@@ -43,6 +56,58 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
     return new EntryRecord(record);
   }
 
+  async createOrderFromTemplate(
+    previousOrderHash: ActionHash,
+    name: string
+  ): Promise<{
+    order: EntryRecord<Order>;
+    availableProducts: Array<EntryRecord<AvailableProducts>>;
+    deletedProducts: Array<ActionHash>;
+    deletedProducers: Array<ActionHash>;
+  }> {
+    const result = await this.callZome("create_order_from_template", {
+      previous_order_hash: previousOrderHash,
+      name,
+    });
+    return {
+      order: new EntryRecord(result.order),
+      availableProducts: result.available_products.map(
+        (r: Record) => new EntryRecord(r)
+      ),
+      deletedProducts: result.deleted_products,
+      deletedProducers: result.deleted_producers,
+    };
+  }
+
+  async getOrderOverview(orderHash: ActionHash): Promise<
+    | {
+        order: EntryRecord<Order>;
+        householdOrders: Array<EntryRecord<HouseholdOrder>>;
+        availableProducts: Array<EntryRecord<AvailableProducts>>;
+        producerDeliveries: Array<EntryRecord<ProducerDelivery>>;
+        producerInvoices: Array<EntryRecord<ProducerInvoice>>;
+      }
+    | undefined
+  > {
+    const overview = await this.callZome("get_order_overview", orderHash);
+    if (!overview) return undefined;
+    return {
+      order: new EntryRecord(overview.order),
+      householdOrders: overview.household_orders.map(
+        (r: Record) => new EntryRecord(r)
+      ),
+      availableProducts: overview.available_products.map(
+        (r: Record) => new EntryRecord(r)
+      ),
+      producerDeliveries: overview.producer_deliveries.map(
+        (r: Record) => new EntryRecord(r)
+      ),
+      producerInvoices: overview.producer_invoices.map(
+        (r: Record) => new EntryRecord(r)
+      ),
+    };
+  }
+
   async getLatestOrder(
     orderHash: ActionHash
   ): Promise<EntryRecord<Order> | undefined> {
@@ -80,6 +145,11 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
     return new EntryRecord(record);
   }
 
+  async closeOrder(orderHash: ActionHash): Promise<EntryRecord<Order>> {
+    const record: Record = await this.callZome("close_order", orderHash);
+    return new EntryRecord(record);
+  }
+
   deleteOrder(originalOrderHash: ActionHash): Promise<ActionHash> {
     return this.callZome("delete_order", originalOrderHash);
   }
@@ -289,6 +359,187 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
   > {
     return this.callZome("get_deleted_household_orders_for_order", orderHash);
   }
+
+  async getRemainingAvailability(
+    orderHash: ActionHash
+  ): Promise<Array<ProductAvailability>> {
+    return this.callZome("get_remaining_availability", orderHash);
+  }
+  /** Standing Order */
+
+  async createStandingOrder(
+    standingOrder: StandingOrder
+  ): Promise<EntryRecord<StandingOrder>> {
+    const record: Record = await this.callZome(
+      "create_standing_order",
+      standingOrder
+    );
+    return new EntryRecord(record);
+  }
+
+  async getLatestStandingOrder(
+    standingOrderHash: ActionHash
+  ): Promise<EntryRecord<StandingOrder> | undefined> {
+    const record: Record = await this.callZome(
+      "get_latest_standing_order",
+      standingOrderHash
+    );
+    return record ? new EntryRecord(record) : undefined;
+  }
+
+  async updateStandingOrder(
+    originalStandingOrderHash: ActionHash,
+    previousStandingOrderHash: ActionHash,
+    updatedStandingOrder: StandingOrder
+  ): Promise<EntryRecord<StandingOrder>> {
+    const record: Record = await this.callZome("update_standing_order", {
+      original_standing_order_hash: originalStandingOrderHash,
+      previous_standing_order_hash: previousStandingOrderHash,
+      updated_standing_order: updatedStandingOrder,
+    });
+    return new EntryRecord(record);
+  }
+
+  deleteStandingOrder(originalStandingOrderHash: ActionHash): Promise<ActionHash> {
+    return this.callZome("delete_standing_order", originalStandingOrderHash);
+  }
+
+  async getStandingOrdersForHousehold(
+    householdHash: ActionHash
+  ): Promise<Array<Link>> {
+    return this.callZome("get_standing_orders_for_household", householdHash);
+  }
+
+  async applyStandingOrder(orderHash: ActionHash): Promise<{
+    householdOrder: EntryRecord<HouseholdOrder> | undefined;
+    skippedLines: Array<SkippedStandingOrderLine>;
+  }> {
+    const result = await this.callZome("apply_standing_order", orderHash);
+    return {
+      householdOrder: result.household_order
+        ? new EntryRecord(result.household_order)
+        : undefined,
+      skippedLines: result.skipped_lines,
+    };
+  }
+
+  /** Household Pickup */
+
+  async createHouseholdPickup(
+    householdPickup: HouseholdPickup
+  ): Promise<EntryRecord<HouseholdPickup>> {
+    const record: Record = await this.callZome(
+      "create_household_pickup",
+      householdPickup
+    );
+    return new EntryRecord(record);
+  }
+
+  async getLatestHouseholdPickup(
+    householdPickupHash: ActionHash
+  ): Promise<EntryRecord<HouseholdPickup> | undefined> {
+    const record: Record = await this.callZome(
+      "get_latest_household_pickup",
+      householdPickupHash
+    );
+    return record ? new EntryRecord(record) : undefined;
+  }
+
+  async updateHouseholdPickup(
+    previousHouseholdPickupHash: ActionHash,
+    updatedHouseholdPickup: HouseholdPickup
+  ): Promise<EntryRecord<HouseholdPickup>> {
+    const record: Record = await this.callZome("update_household_pickup", {
+      previous_household_pickup_hash: previousHouseholdPickupHash,
+      updated_household_pickup: updatedHouseholdPickup,
+    });
+    return new EntryRecord(record);
+  }
+
+  deleteHouseholdPickup(
+    originalHouseholdPickupHash: ActionHash
+  ): Promise<ActionHash> {
+    return this.callZome(
+      "delete_household_pickup",
+      originalHouseholdPickupHash
+    );
+  }
+
+  async getHouseholdPickupsForOrder(
+    orderHash: ActionHash
+  ): Promise<Array<Link>> {
+    return this.callZome("get_household_pickups_for_order", orderHash);
+  }
+
+  async getHouseholdPickupsForHousehold(
+    householdHash: ActionHash
+  ): Promise<Array<Link>> {
+    return this.callZome("get_household_pickups_for_household", householdHash);
+  }
+
+  /** Household Payment */
+
+  async createHouseholdPayment(
+    householdPayment: HouseholdPayment
+  ): Promise<EntryRecord<HouseholdPayment>> {
+    const record: Record = await this.callZome(
+      "create_household_payment",
+      householdPayment
+    );
+    return new EntryRecord(record);
+  }
+
+  async getLatestHouseholdPayment(
+    householdPaymentHash: ActionHash
+  ): Promise<EntryRecord<HouseholdPayment> | undefined> {
+    const record: Record = await this.callZome(
+      "get_latest_household_payment",
+      householdPaymentHash
+    );
+    return record ? new EntryRecord(record) : undefined;
+  }
+
+  async updateHouseholdPayment(
+    previousHouseholdPaymentHash: ActionHash,
+    updatedHouseholdPayment: HouseholdPayment
+  ): Promise<EntryRecord<HouseholdPayment>> {
+    const record: Record = await this.callZome("update_household_payment", {
+      previous_household_payment_hash: previousHouseholdPaymentHash,
+      updated_household_payment: updatedHouseholdPayment,
+    });
+    return new EntryRecord(record);
+  }
+
+  deleteHouseholdPayment(
+    originalHouseholdPaymentHash: ActionHash
+  ): Promise<ActionHash> {
+    return this.callZome(
+      "delete_household_payment",
+      originalHouseholdPaymentHash
+    );
+  }
+
+  getOldestDeleteForHouseholdPayment(
+    originalHouseholdPaymentHash: ActionHash
+  ): Promise<SignedActionHashed<Delete> | undefined> {
+    return this.callZome(
+      "get_oldest_delete_for_household_payment",
+      originalHouseholdPaymentHash
+    );
+  }
+
+  async getHouseholdPaymentsForOrder(
+    orderHash: ActionHash
+  ): Promise<Array<Link>> {
+    return this.callZome("get_household_payments_for_order", orderHash);
+  }
+
+  async getHouseholdPaymentsForHousehold(
+    householdHash: ActionHash
+  ): Promise<Array<Link>> {
+    return this.callZome("get_household_payments_for_household", householdHash);
+  }
+
   /** Producer Delivery */
 
   async createProducerDelivery(
@@ -369,6 +620,12 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
     );
   }
 
+  async validateDeliveryConsistency(
+    orderHash: ActionHash
+  ): Promise<Array<DeliveryInconsistency>> {
+    return this.callZome("validate_delivery_consistency", orderHash);
+  }
+
   async getProducerDeliveriesForOrder(
     orderHash: ActionHash
   ): Promise<Array<Link>> {
@@ -465,6 +722,48 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
     );
   }
 
+  async draftProducerInvoiceLines(
+    orderHash: ActionHash,
+    producerHash: ActionHash
+  ): Promise<Array<InvoiceLine>> {
+    return this.callZome("draft_producer_invoice_lines", {
+      order_hash: orderHash,
+      producer_hash: producerHash,
+    });
+  }
+
+  async getPackingList(orderHash: ActionHash): Promise<PackingList> {
+    return this.callZome("get_packing_list", orderHash);
+  }
+
+  async computeHouseholdBill(
+    orderHash: ActionHash,
+    householdHash: ActionHash
+  ): Promise<HouseholdBill> {
+    return this.callZome("compute_household_bill", {
+      order_hash: orderHash,
+      household_hash: householdHash,
+    });
+  }
+
+  async computeOrderBill(orderHash: ActionHash): Promise<OrderBill> {
+    return this.callZome("compute_order_bill", orderHash);
+  }
+
+  async reconcileOrder(orderHash: ActionHash): Promise<OrderReconciliation> {
+    return this.callZome("reconcile_order", orderHash);
+  }
+
+  async getOrdersForProduct(
+    originalProductHash: ActionHash
+  ): Promise<Array<ProductRevisionOrders>> {
+    return this.callZome("get_orders_for_product", originalProductHash);
+  }
+
+  async getClubSettings(): Promise<ClubSettings> {
+    return this.callZome("get_club_settings", undefined);
+  }
+
   async getProducerInvoicesForOrder(
     orderHash: ActionHash
   ): Promise<Array<Link>> {
@@ -484,4 +783,16 @@ export class OrdersClient extends ZomeClient<OrdersSignal> {
   async getAllOrders(): Promise<Array<Link>> {
     return this.callZome("get_all_orders", undefined);
   }
+
+  async getOrdersInRange(from: number, to: number): Promise<Array<Link>> {
+    return this.callZome("get_orders_in_range", { from, to });
+  }
+
+  async getOrdersByStatus(statusName: string): Promise<Array<Link>> {
+    return this.callZome("get_orders_by_status", statusName);
+  }
+
+  async migrateAllOrders(): Promise<number> {
+    return this.callZome("migrate_all_orders", undefined);
+  }
 }
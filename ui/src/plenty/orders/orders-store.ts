@@ -259,7 +259,7 @@ export class OrdersStore {
     collectionSignal(
       this.client,
       () => this.client.getAllOrders(),
-      "AllOrders",
+      "OrdersByStatus",
     ),
     (allOrders) =>
       slice(
@@ -13,6 +13,7 @@ import {
 } from "@holochain/client";
 import { ActionCommittedSignal, HoloHashMap } from "@holochain-open-dev/utils";
 import { msg } from "@lit/localize";
+import { Packaging } from "../producers/types.js";
 import { TemplateResult, html } from "lit";
 
 export type OrdersSignal = ActionCommittedSignal<EntryTypes, LinkTypes>;
@@ -22,7 +23,10 @@ export type EntryTypes =
   | ({ type: "ProducerInvoice" } & ProducerInvoice)
   | ({ type: "ProducerDelivery" } & ProducerDelivery)
   | ({ type: "HouseholdOrder" } & HouseholdOrder)
-  | ({ type: "Order" } & Order);
+  | ({ type: "Order" } & Order)
+  | ({ type: "HouseholdPayment" } & HouseholdPayment)
+  | ({ type: "StandingOrder" } & StandingOrder)
+  | ({ type: "HouseholdPickup" } & HouseholdPickup);
 
 export type LinkTypes = string;
 
@@ -89,10 +93,19 @@ export interface ProductOrder {
 
 export interface HouseholdOrder {
   order_hash: ActionHash;
+  latest_order_hash: ActionHash; // Set to the latest revision of the order by the zome
   household_hash: ActionHash;
   products: Array<ProductOrder>;
 }
 
+export interface ProductAvailability {
+  original_product_hash: ActionHash;
+  maximum_available: number | undefined;
+  ordered_amount: number;
+  remaining: number | undefined; // Negative if the product was oversubscribed
+  oversubscribed: boolean;
+}
+
 export interface FixedProductDeliveryForHouseholds {
   amount: number;
   households_hashes: Array<ActionHash>;
@@ -111,7 +124,7 @@ export type DeliveredAmount =
     }
   | {
       type: "EstimatedAmountProduct";
-      delivered_products: Array<EstimatedProductDeliveryForHouseholds>;
+      delivered_products_by_household: Array<EstimatedProductDeliveryForHouseholds>;
       price_cents_per_unit_changed: number | undefined;
     };
 
@@ -128,13 +141,135 @@ export type ProductDelivery =
 export interface ProducerDelivery {
   order_hash: ActionHash;
   producer_hash: ActionHash;
+  latest_producer_hash: ActionHash;
   products: Record<ActionHashB64, ProductDelivery>;
 }
 
+export interface InvoiceLine {
+  original_product_hash: ActionHash;
+  quantity: number;
+  unit_price_cents: number;
+  vat_percentage: number;
+  subtotal_cents: number;
+  vat_cents: number;
+  total_cents: number;
+}
+
+export interface PackingListLine {
+  original_product_hash: ActionHash;
+  product_name: string;
+  packaging: Packaging;
+  ordered_amount: number;
+  delivered_amount: number | undefined;
+}
+
+export interface HouseholdPackingList {
+  household_hash: ActionHash;
+  household_name: string;
+  lines: Array<PackingListLine>;
+}
+
+export interface ProducerPickingList {
+  producer_hash: ActionHash;
+  producer_name: string;
+  lines: Array<PackingListLine>;
+}
+
+export interface PackingList {
+  order_hash: ActionHash;
+  households: Array<HouseholdPackingList>;
+  producers: Array<ProducerPickingList>;
+}
+
+export type DeliveryInconsistency =
+  | {
+      type: "ProductNotOrdered";
+      producer_delivery_hash: ActionHash;
+      original_product_hash: ActionHash;
+    }
+  | {
+      type: "HouseholdDidNotOrderProduct";
+      producer_delivery_hash: ActionHash;
+      original_product_hash: ActionHash;
+      household_hash: ActionHash;
+    }
+  | {
+      type: "DeliveredMoreThanOrdered";
+      producer_delivery_hash: ActionHash;
+      original_product_hash: ActionHash;
+      ordered_amount: number;
+      delivered_amount: number;
+    };
+
+export interface HouseholdBillLine {
+  original_product_hash: ActionHash;
+  ordered_product_hash: ActionHash;
+  name: string;
+  estimate: boolean;
+  ordered_amount: number;
+  delivered_amount: number | undefined;
+  price_cents_per_unit: number;
+  price_changed: boolean;
+  subtotal_cents: number;
+  margin_cents: number;
+  vat_cents: number;
+  total_cents: number;
+}
+
+export interface HouseholdBill {
+  order_hash: ActionHash;
+  household_hash: ActionHash;
+  lines: Array<HouseholdBillLine>;
+  subtotal_cents: number;
+  margin_cents: number;
+  vat_cents: number;
+  total_cents: number;
+}
+
+export interface OrderBill {
+  order_hash: ActionHash;
+  households: Array<HouseholdBill>;
+  total_cents: number;
+}
+
+export interface ProductDifference {
+  original_product_hash: ActionHash;
+  delivered_quantity: number;
+  invoiced_quantity: number;
+  catalog_price_cents: number;
+  delivered_price_cents: number | undefined;
+  invoiced_price_cents: number | undefined;
+  expected_total_cents: number;
+  invoiced_total_cents: number;
+}
+
+export interface ProducerReconciliation {
+  producer_hash: ActionHash;
+  producer_delivery_hash: ActionHash | undefined;
+  producer_invoices_hashes: Array<ActionHash>;
+  status: {
+    type:
+      | "Matching"
+      | "Mismatch"
+      | "MissingInvoice"
+      | "MissingDelivery"
+      | "InvoiceWithoutLines";
+  };
+  expected_total_cents: number;
+  invoiced_total_cents: number;
+  differences: Array<ProductDifference>;
+}
+
+export interface OrderReconciliation {
+  order_hash: ActionHash;
+  producers: Array<ProducerReconciliation>;
+}
+
 export interface ProducerInvoice {
   order_hash: ActionHash;
   producer_hash: ActionHash;
   invoice: EntryHash;
+  lines: Array<InvoiceLine>;
 }
 
 export type ProducerAvailability =
@@ -152,3 +287,77 @@ export interface AvailableProducts {
   latest_producer_hash: ActionHash;
   producer_availability: ProducerAvailability;
 }
+
+export type PaymentMethod =
+  | {
+      type: "Cash";
+    }
+  | {
+      type: "BankTransfer";
+    }
+  | {
+      type: "Other";
+      description: string;
+    };
+
+export interface HouseholdPayment {
+  order_hash: ActionHash;
+  household_hash: ActionHash;
+  amount_cents: number;
+  payment_method: PaymentMethod;
+}
+
+export type PickupExceptionKind =
+  | {
+      type: "Missing";
+    }
+  | {
+      type: "Damaged";
+    }
+  | {
+      type: "Other";
+      description: string;
+    };
+
+export interface PickupException {
+  original_product_hash: ActionHash;
+  amount: number;
+  kind: PickupExceptionKind;
+  comment: string | undefined;
+}
+
+export interface HouseholdPickup {
+  order_hash: ActionHash;
+  household_hash: ActionHash;
+  picked_up_at: number;
+  handed_out_by: AgentPubKey;
+  exceptions: Array<PickupException>;
+}
+
+export interface StandingProductOrder {
+  original_product_hash: ActionHash;
+  amount: number;
+}
+
+export interface StandingOrder {
+  household_hash: ActionHash;
+  products: Array<StandingProductOrder>;
+}
+
+export interface SkippedStandingOrderLine {
+  original_product_hash: ActionHash;
+  amount: number;
+  reason:
+    | { type: "NotOffered" }
+    | { type: "NotEnoughAvailable"; remaining: number };
+}
+
+export interface ClubSettings {
+  currency_code: string;
+  decimal_places: number;
+}
+
+export interface ProductRevisionOrders {
+  ordered_product_hash: ActionHash;
+  orders_hashes: Array<ActionHash>;
+}
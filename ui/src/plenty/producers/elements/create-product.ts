@@ -112,6 +112,7 @@ export class CreateProduct extends SignalWatcher(LitElement) {
 
     const product: Product = {
       producer_hash: this.producerHash!,
+      latest_producer_hash: this.producerHash!,
       name: fields.name!,
       product_id: fields.product_id!,
       description: fields.description!,
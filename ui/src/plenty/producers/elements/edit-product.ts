@@ -132,6 +132,7 @@ export class EditProduct extends SignalWatcher(LitElement) {
 
     const product: Product = {
       producer_hash: currentRecord.entry.producer_hash!,
+      latest_producer_hash: currentRecord.entry.latest_producer_hash!,
       name: fields.name!,
       product_id: fields.product_id!,
       description: fields.description!,
@@ -345,11 +345,13 @@ export async function sampleProduct(
   client: ProducersClient,
   partialProduct: Partial<Product> = {},
 ): Promise<Product> {
+  const producer_hash =
+    partialProduct.producer_hash ||
+    (await client.createProducer(await sampleProducer(client))).actionHash;
   return {
     ...{
-      producer_hash:
-        partialProduct.producer_hash ||
-        (await client.createProducer(await sampleProducer(client))).actionHash,
+      producer_hash,
+      latest_producer_hash: producer_hash,
       name: "Lorem ipsum 2",
       product_id: "Lorem ipsum 2",
       description: "Lorem ipsum 2",
@@ -1,4 +1,4 @@
-import { Product } from "./types.js";
+import { ImportProductsOutput, PriceChange, Product } from "./types.js";
 
 import { Producer } from "./types.js";
 
@@ -164,6 +164,31 @@ export class ProducersClient extends ZomeClient<ProducersSignal> {
     return records.map((r) => new EntryRecord(r));
   }
 
+  async importProducts(
+    producerHash: ActionHash,
+    products: Array<Product>
+  ): Promise<ImportProductsOutput> {
+    return this.callZome("import_products", {
+      producer_hash: producerHash,
+      products,
+    });
+  }
+
+  async searchProducts(query: string): Promise<Array<EntryRecord<Product>>> {
+    const records: Record[] = await this.callZome("search_products", query);
+    return records.map((r) => new EntryRecord(r));
+  }
+
+  async migrateProductNamesIndex(): Promise<number> {
+    return this.callZome("migrate_product_names_index", undefined);
+  }
+
+  async getPriceHistory(
+    originalProductHash: ActionHash
+  ): Promise<Array<PriceChange>> {
+    return this.callZome("get_price_history", originalProductHash);
+  }
+
   async updateProduct(
     originalProductHash: ActionHash,
     previousProductHash: ActionHash,
@@ -214,4 +239,22 @@ export class ProducersClient extends ZomeClient<ProducersSignal> {
   async getProductsForCategory(category: String): Promise<Array<Link>> {
     return this.callZome("get_products_for_category", category);
   }
+
+  async getSubcategories(category: string): Promise<Array<string>> {
+    return this.callZome("get_subcategories", category);
+  }
+
+  async renameCategory(from: string, to: string): Promise<void> {
+    return this.callZome("rename_category", {
+      from,
+      to,
+    });
+  }
+
+  async mergeCategories(categories: Array<string>, into: string): Promise<void> {
+    return this.callZome("merge_categories", {
+      categories,
+      into,
+    });
+  }
 }
@@ -91,6 +91,8 @@ export interface Packaging {
 
 export interface Product {
   producer_hash: ActionHash;
+  // Set by the zome to the latest revision of the producer
+  latest_producer_hash: ActionHash;
 
   name: string;
   product_id: string;
@@ -104,3 +106,35 @@ export interface Product {
   origin: string | undefined;
   ingredients: string | undefined;
 }
+
+export interface PriceChange {
+  product_hash: ActionHash;
+  author: AgentPubKey;
+  timestamp: number;
+  price_cents: number;
+  vat_percentage: number;
+  margin_percentage: number | undefined;
+  orders_hashes: Array<ActionHash>;
+}
+
+export interface CatalogRowError {
+  line: number;
+  message: string;
+}
+
+// A product as read from a catalog, which doesn't know about its producer
+export type CatalogProduct = Omit<
+  Product,
+  "producer_hash" | "latest_producer_hash"
+>;
+
+export interface CatalogImport {
+  products: Array<CatalogProduct>;
+  errors: Array<CatalogRowError>;
+}
+
+export interface ImportProductsOutput {
+  created: Array<ActionHash>;
+  updated: Array<ActionHash>;
+  unchanged: Array<ActionHash>;
+}
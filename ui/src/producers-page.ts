@@ -2,6 +2,7 @@ import { SignalWatcher, pipe } from "@holochain-open-dev/signals";
 import { css, LitElement, html } from "lit";
 import { customElement, state } from "lit/decorators.js";
 import { consume } from "@lit/context";
+import "@shoelace-style/shoelace/dist/components/alert/alert.js";
 import "@shoelace-style/shoelace/dist/components/button/button.js";
 import "@shoelace-style/shoelace/dist/components/breadcrumb/breadcrumb.js";
 import "@shoelace-style/shoelace/dist/components/icon/icon.js";
@@ -11,6 +12,7 @@ import "@holochain-open-dev/file-storage/dist/elements/show-image.js";
 import "@holochain-open-dev/profiles/dist/elements/profile-list-item.js";
 import { msg, str } from "@lit/localize";
 import {
+  notify,
   notifyError,
   Router,
   sharedStyles,
@@ -39,6 +41,9 @@ import {
 import { producersStoreContext } from "./plenty/producers/context.js";
 import { ProducersStore } from "./plenty/producers/producers-store.js";
 import {
+  CatalogImport,
+  CatalogProduct,
+  CatalogRowError,
   Producer,
   Product,
   renderPackaging,
@@ -51,7 +56,8 @@ import {
   rolesStoreContext,
 } from "@darksoil-studio/roles";
 import { SlDialog } from "@shoelace-style/shoelace";
-import { processCsvProductsFile } from "./utils.js";
+import { readAsText } from "./utils.js";
+import { core } from "@tauri-apps/api";
 import { GridDataProviderCallback } from "@vaadin/grid";
 
 @customElement("producers-page")
@@ -301,22 +307,44 @@ export class ProducersPage extends SignalWatcher(LitElement) {
   }
 
   @state()
-  uploadedProducts: Array<Omit<Product, "producer_hash">> | undefined;
+  uploadedProducts: Array<CatalogProduct> | undefined;
+
+  @state()
+  importErrors: Array<CatalogRowError> = [];
 
   @state()
   uploading = false;
 
+  async parseCsvProductsFile(file: File) {
+    const csv = await readAsText(file);
+    const catalog: CatalogImport = await core.invoke("parse_product_catalog", {
+      csv,
+    });
+    this.uploadedProducts = catalog.products;
+    this.importErrors = catalog.errors;
+  }
+
   async uploadProducts(
     producerHash: ActionHash,
-    products: Array<Omit<Product, "producer_hash">>,
+    products: Array<CatalogProduct>,
   ) {
     if (this.uploading) return;
 
     this.uploading = true;
 
     try {
-      await this.producersStore.client.createProducts(
-        products.map((p) => ({ ...p, producer_hash: producerHash })),
+      const result = await this.producersStore.client.importProducts(
+        producerHash,
+        products.map((p) => ({
+          ...p,
+          producer_hash: producerHash,
+          latest_producer_hash: producerHash,
+        })),
+      );
+      notify(
+        msg(
+          str`Imported products: ${result.created.length} created, ${result.updated.length} updated and ${result.unchanged.length} unchanged.`,
+        ),
       );
       (
         this.shadowRoot?.getElementById(
@@ -332,7 +360,27 @@ export class ProducersPage extends SignalWatcher(LitElement) {
     this.uploading = false;
   }
 
-  renderGrid(products: Array<Omit<Product, "producer_hash">>) {
+  renderImportErrors(errors: Array<CatalogRowError>) {
+    return html`
+      <sl-alert variant="danger" open>
+        <div class="column" style="gap: 8px">
+          <span
+            >${msg(
+              "Fix these errors in the CSV file and upload it again before importing the products:",
+            )}</span
+          >
+          ${errors.map(
+            (error) =>
+              html`<span
+                >${msg(str`Line ${error.line}`)}: ${error.message}</span
+              >`,
+          )}
+        </div>
+      </sl-alert>
+    `;
+  }
+
+  renderGrid(products: Array<CatalogProduct>) {
     return html`
       <div class="column" style="gap: 12px; flex: 1">
         <span class="title">${msg("Preview")}</span>
@@ -390,6 +438,7 @@ export class ProducersPage extends SignalWatcher(LitElement) {
           ) as HTMLInputElement;
           input.value = "";
           this.uploadedProducts = undefined;
+          this.importErrors = [];
         }}
         style="--width: 800px;"
       >
@@ -409,9 +458,10 @@ export class ProducersPage extends SignalWatcher(LitElement) {
                   const file = (event.target as any).files[0];
                   if (!file) {
                     this.uploadedProducts = undefined;
+                    this.importErrors = [];
                     return;
                   }
-                  this.uploadedProducts = await processCsvProductsFile(file);
+                  await this.parseCsvProductsFile(file);
                 } catch (e: any) {
                   notifyError(
                     msg(str`Error processing the CSV file: ${e.message}`),
@@ -430,12 +480,16 @@ export class ProducersPage extends SignalWatcher(LitElement) {
                       ) as HTMLInputElement;
                       input.value = "";
                       this.uploadedProducts = undefined;
+                      this.importErrors = [];
                     }}
                     >${msg("Clear")}</sl-button
                   >
                 `
               : html``}
           </div>
+          ${this.importErrors.length > 0
+            ? this.renderImportErrors(this.importErrors)
+            : html``}
           ${this.uploadedProducts
             ? this.renderGrid(this.uploadedProducts)
             : html`
@@ -477,7 +531,9 @@ export class ProducersPage extends SignalWatcher(LitElement) {
         <sl-button
           slot="footer"
           .loading=${this.uploading}
-          .disabled=${!this.uploadedProducts}
+          .disabled=${!this.uploadedProducts ||
+          this.uploadedProducts.length === 0 ||
+          this.importErrors.length > 0}
           @click=${() =>
             this.uploadProducts(producerHash, this.uploadedProducts!)}
           variant="primary"